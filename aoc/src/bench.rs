@@ -0,0 +1,292 @@
+//! Timing parse, part 1 and part 2 of each day.
+//!
+//! Results are written as tab separated values, one line per day and stage,
+//! with the columns `day stage iterations min_ns median_ns max_ns` and the
+//! stage being one of `parse`, `part1` or `part2`.
+//!
+//! A saved results file can be used as a baseline for a later run, which then
+//! flags every stage whose median got slower than the threshold allows.
+
+use common::parse::{ParseError, Span};
+use common::{AocError, DynSolution, Part};
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// What's being timed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stage {
+    Parse,
+    Solve(Part),
+}
+
+impl Stage {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "parse" => Some(Stage::Parse),
+            "part1" => Some(Stage::Solve(Part::One)),
+            "part2" => Some(Stage::Solve(Part::Two)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Solve(part) => write!(f, "part{part}"),
+        }
+    }
+}
+
+/// How long one stage of a day took over several iterations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Timing {
+    pub day: u8,
+    pub stage: Stage,
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timing {
+    fn from_samples(day: u8, stage: Stage, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Timing {
+            day,
+            stage,
+            iterations: samples.len(),
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+impl fmt::Display for Timing {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Day {:>2} {:<5}  min {:>10}  median {:>10}  max {:>10}",
+            self.day,
+            self.stage,
+            format_duration(self.min),
+            format_duration(self.median),
+            format_duration(self.max)
+        )
+    }
+}
+
+/// Durations in the most readable unit, like `1.25ms`
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    match nanos {
+        x if x < 1e3 => format!("{x:.0}ns"),
+        x if x < 1e6 => format!("{:.2}µs", x / 1e3),
+        x if x < 1e9 => format!("{:.2}ms", x / 1e6),
+        x => format!("{:.2}s", x / 1e9),
+    }
+}
+
+/// Time `f` for each iteration
+fn sample<T>(iterations: usize, mut f: impl FnMut() -> T) -> Vec<Duration> {
+    (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            black_box(f());
+            start.elapsed()
+        })
+        .collect()
+}
+
+/// Time parsing `text` and solving both parts, `iterations` times each
+pub fn bench_day(
+    solution: &dyn DynSolution,
+    text: &str,
+    iterations: usize,
+) -> Result<Vec<Timing>, AocError> {
+    let iterations = iterations.max(1);
+    let day = solution.day();
+
+    // Fail early rather than timing errors
+    let parsed = solution.parse(text)?;
+    for part in Part::BOTH {
+        solution.solve(parsed.as_ref(), part)?;
+    }
+
+    let mut timings = vec![Timing::from_samples(
+        day,
+        Stage::Parse,
+        sample(iterations, || solution.parse(black_box(text))),
+    )];
+    for part in Part::BOTH {
+        timings.push(Timing::from_samples(
+            day,
+            Stage::Solve(part),
+            sample(iterations, || solution.solve(parsed.as_ref(), part)),
+        ));
+    }
+    Ok(timings)
+}
+
+const HEADER: &str = "day\tstage\titerations\tmin_ns\tmedian_ns\tmax_ns";
+
+pub fn format_results(timings: &[Timing]) -> String {
+    let mut text = format!("{HEADER}\n");
+    for x in timings {
+        text.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\n",
+            x.day,
+            x.stage,
+            x.iterations,
+            x.min.as_nanos(),
+            x.median.as_nanos(),
+            x.max.as_nanos()
+        ));
+    }
+    text
+}
+
+fn parse_timing(line: Span) -> Result<Timing, ParseError> {
+    let fields: Vec<Span> = line.split("\t").collect();
+    let [day, stage, iterations, min, median, max] = fields[..] else {
+        return Err(line.error("expected 6 tab separated fields"));
+    };
+    let nanos = |field: Span| field.parse::<u64>().map(Duration::from_nanos);
+    Ok(Timing {
+        day: day.parse()?,
+        stage: Stage::from_name(stage.as_str())
+            .ok_or_else(|| stage.error("expected parse, part1 or part2"))?,
+        iterations: iterations.parse()?,
+        min: nanos(min)?,
+        median: nanos(median)?,
+        max: nanos(max)?,
+    })
+}
+
+pub fn parse_results(text: &str) -> Result<Vec<Timing>, ParseError> {
+    Span::new(text)
+        .lines()
+        .filter(|line| line.as_str() != HEADER && !line.trim().is_empty())
+        .map(parse_timing)
+        .collect()
+}
+
+pub fn read_results(path: &Path) -> Result<Vec<Timing>, AocError> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+    parse_results(&text).map_err(|e| AocError::from(e).in_file(path.display()))
+}
+
+/// A stage whose median is slower than in the baseline
+#[derive(Debug, PartialEq)]
+pub struct Regression {
+    pub day: u8,
+    pub stage: Stage,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Regression {
+    /// How much slower as a fraction of the baseline, 0.5 is 50% slower
+    pub fn slowdown(&self) -> f64 {
+        self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0
+    }
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Day {} {} median {} vs baseline {} ({:+.0}%)",
+            self.day,
+            self.stage,
+            format_duration(self.current),
+            format_duration(self.baseline),
+            self.slowdown() * 100.0
+        )
+    }
+}
+
+/// Stages more than `threshold` (0.1 is 10%) slower than the baseline.
+///
+/// Stages missing from the baseline are skipped.
+pub fn regressions(current: &[Timing], baseline: &[Timing], threshold: f64) -> Vec<Regression> {
+    current
+        .iter()
+        .filter_map(|now| {
+            let before = baseline
+                .iter()
+                .find(|x| x.day == now.day && x.stage == now.stage)?;
+            let regression = Regression {
+                day: now.day,
+                stage: now.stage,
+                baseline: before.median,
+                current: now.median,
+            };
+            (regression.slowdown() > threshold).then_some(regression)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(day: u8, stage: Stage, median_us: u64) -> Timing {
+        Timing {
+            day,
+            stage,
+            iterations: 3,
+            min: Duration::from_micros(median_us - 1),
+            median: Duration::from_micros(median_us),
+            max: Duration::from_micros(median_us + 1),
+        }
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let timings = vec![
+            timing(6, Stage::Parse, 10),
+            timing(14, Stage::Solve(Part::Two), 250),
+        ];
+        let text = format_results(&timings);
+        assert!(text.starts_with("day\tstage"));
+        assert_eq!(parse_results(&text).unwrap(), timings);
+
+        let error = parse_results(&format!("{HEADER}\n6\tparse\t3\n")).unwrap_err();
+        assert_eq!(error.line, 2);
+    }
+
+    #[test]
+    fn flags_slower_medians() {
+        let baseline = vec![
+            timing(6, Stage::Parse, 100),
+            timing(6, Stage::Solve(Part::One), 100),
+        ];
+        let current = vec![
+            timing(6, Stage::Parse, 105),
+            timing(6, Stage::Solve(Part::One), 150),
+            timing(7, Stage::Parse, 1000),
+        ];
+        let found = regressions(&current, &baseline, 0.1);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].stage, Stage::Solve(Part::One));
+        assert!((found[0].slowdown() - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn samples_every_iteration() {
+        let timing = Timing::from_samples(
+            1,
+            Stage::Parse,
+            sample(5, || std::thread::sleep(Duration::from_micros(10))),
+        );
+        assert_eq!(timing.iterations, 5);
+        assert!(timing.min <= timing.median && timing.median <= timing.max);
+    }
+}
@@ -0,0 +1,58 @@
+use common::{register, DynSolution};
+
+/// Every solved day, in calendar order.
+///
+/// Days that were split into a `dayN` and `dayN_2` crate are registered
+/// through the `_2` crate, which solves both parts.
+pub fn all() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        register::<day1_2::Day1>(),
+        register::<day2_2::Day2>(),
+        register::<day3_2::Day3>(),
+        register::<day4::Day4>(),
+        register::<day5_2::Day5>(),
+        register::<day6_2::Day6>(),
+        register::<day7::Day7>(),
+        register::<day8::Day8>(),
+        register::<day9::Day9>(),
+        register::<day10::Day10>(),
+        register::<day11::Day11>(),
+        register::<day12::Day12>(),
+        register::<day13::Day13>(),
+        register::<day14::Day14>(),
+        register::<day15::Day15>(),
+        register::<day16::Day16>(),
+        register::<day17::Day17>(),
+    ]
+}
+
+pub fn find(day: u8) -> Option<Box<dyn DynSolution>> {
+    all().into_iter().find(|solution| solution.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::random::Rng;
+    use common::Part;
+
+    #[test]
+    fn generated_inputs_solve() {
+        for solution in all() {
+            let day = solution.day();
+            for seed in 0..3 {
+                let input = solution
+                    .generate(None, &mut Rng::new(seed))
+                    .unwrap_or_else(|| panic!("day {day} has no generator"));
+                let parsed = solution
+                    .parse(&input)
+                    .unwrap_or_else(|e| panic!("day {day} seed {seed}: {e}"));
+                for part in Part::BOTH {
+                    if let Err(e) = solution.solve(parsed.as_ref(), part) {
+                        panic!("day {day} seed {seed} part {part}: {e}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,732 @@
+//! `aoc explore`, a terminal UI for picking a day, an input and a part, then
+//! browsing what comes out: the answer, a picture for days that draw one and
+//! a collapsible tree for days with an outline.
+//!
+//! There are no dependencies to lean on, so the terminal goes into raw mode
+//! through `stty` and everything is drawn with ANSI escape codes.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use common::outline::Outline;
+use common::render::{Color, Picture};
+use common::{Answer, AocError, DynSolution, Part};
+
+/// A key press read from the terminal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Tab,
+    Escape,
+    Char(char),
+}
+
+/// Decode the bytes of one read from the terminal. Escape sequences for keys
+/// we don't use are dropped.
+fn decode_keys(bytes: &[u8]) -> Vec<Key> {
+    let text = String::from_utf8_lossy(bytes);
+    let mut chars = text.chars().peekable();
+    let mut keys: Vec<Key> = Vec::new();
+    while let Some(c) = chars.next() {
+        let key = match c {
+            '\x1b' if matches!(chars.peek(), Some('[' | 'O')) => {
+                chars.next();
+                let mut parameter = String::new();
+                while let Some(&x) = chars.peek().filter(|x| x.is_ascii_digit() || **x == ';') {
+                    parameter.push(x);
+                    chars.next();
+                }
+                match (chars.next(), parameter.as_str()) {
+                    (Some('A'), _) => Key::Up,
+                    (Some('B'), _) => Key::Down,
+                    (Some('C'), _) => Key::Right,
+                    (Some('D'), _) => Key::Left,
+                    (Some('H'), _) | (Some('~'), "1" | "7") => Key::Home,
+                    (Some('F'), _) | (Some('~'), "4" | "8") => Key::End,
+                    (Some('~'), "5") => Key::PageUp,
+                    (Some('~'), "6") => Key::PageDown,
+                    _ => continue,
+                }
+            }
+            '\x1b' => Key::Escape,
+            '\r' | '\n' => Key::Enter,
+            '\t' => Key::Tab,
+            c => Key::Char(c),
+        };
+        keys.push(key);
+    }
+    keys
+}
+
+/// A list with one entry selected, scrolled to keep it in view
+#[derive(Debug, Default)]
+struct Menu {
+    selected: usize,
+    top: usize,
+}
+
+impl Menu {
+    /// Move the selection for the arrow, page, home and end keys, returning
+    /// false for any other key
+    fn handle(&mut self, key: Key, len: usize, page: usize) -> bool {
+        let last = len.saturating_sub(1);
+        self.selected = match key {
+            Key::Up => self.selected.saturating_sub(1),
+            Key::Down => (self.selected + 1).min(last),
+            Key::PageUp => self.selected.saturating_sub(page),
+            Key::PageDown => (self.selected + page).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            _ => return false,
+        };
+        self.top = self.top(page);
+        true
+    }
+
+    /// First entry to show so the selection fits in `page` lines
+    fn top(&self, page: usize) -> usize {
+        match self.selected {
+            x if x < self.top => x,
+            x if x >= self.top + page => x + 1 - page.max(1),
+            _ => self.top,
+        }
+    }
+}
+
+/// An [Outline] with some of its nodes expanded
+struct Tree {
+    outline: Outline,
+    /// Paths of child indices from the root
+    expanded: HashSet<Vec<usize>>,
+    cursor: Menu,
+}
+
+/// A visible line of a [Tree]
+struct Row<'a> {
+    path: Vec<usize>,
+    node: &'a Outline,
+}
+
+impl Tree {
+    /// Starts with only the root expanded
+    fn new(outline: Outline) -> Self {
+        Tree {
+            outline,
+            expanded: HashSet::from([Vec::new()]),
+            cursor: Menu::default(),
+        }
+    }
+
+    /// Nodes that aren't inside a collapsed one, in order
+    fn rows(&self) -> Vec<Row<'_>> {
+        let mut rows: Vec<Row> = Vec::new();
+        let mut stack = vec![Row {
+            path: Vec::new(),
+            node: &self.outline,
+        }];
+        while let Some(row) = stack.pop() {
+            if self.expanded.contains(&row.path) {
+                for (i, child) in row.node.children.iter().enumerate().rev() {
+                    let mut path = row.path.clone();
+                    path.push(i);
+                    stack.push(Row { path, node: child });
+                }
+            }
+            rows.push(row);
+        }
+        rows
+    }
+
+    fn handle(&mut self, key: Key, page: usize) {
+        let len = self.rows().len();
+        if self.cursor.handle(key, len, page) {
+            return;
+        }
+        let rows = self.rows();
+        let Some(row) = rows.get(self.cursor.selected) else {
+            return;
+        };
+        let path = row.path.clone();
+        let has_children = !row.node.children.is_empty();
+        let parent = path
+            .split_last()
+            .and_then(|(_, parent)| rows.iter().position(|x| x.path == parent));
+        let expanded = self.expanded.contains(&path);
+        match key {
+            Key::Enter | Key::Char(' ') | Key::Left if has_children && expanded => {
+                self.expanded.remove(&path);
+            }
+            Key::Enter | Key::Char(' ') | Key::Right if has_children && !expanded => {
+                self.expanded.insert(path);
+            }
+            Key::Right if has_children => {
+                self.cursor.handle(Key::Down, len, page);
+            }
+            Key::Left => {
+                if let Some(parent) = parent {
+                    self.cursor.selected = parent;
+                    self.cursor.top = self.cursor.top(page);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn draw(&self, lines: &mut Vec<String>, page: usize, width: usize) {
+        let rows = self.rows();
+        let top = self.cursor.top(page);
+        for (i, row) in rows.iter().enumerate().skip(top).take(page) {
+            let marker = match (row.node.children.len(), self.expanded.contains(&row.path)) {
+                (0, _) => "  ".to_owned(),
+                (_, true) => "- ".to_owned(),
+                (n, false) => format!("+ ({n}) "),
+            };
+            let text = format!(
+                "{:indent$}{marker}{}",
+                "",
+                row.node.label,
+                indent = row.path.len() * 2
+            );
+            lines.push(highlight(&fit(&text, width), i == self.cursor.selected));
+        }
+    }
+}
+
+/// One way of looking at a part's result
+enum View {
+    /// The answer and how long it took, scrolled down by `top` lines
+    Answer {
+        lines: Vec<String>,
+        top: usize,
+    },
+    Picture {
+        picture: Picture,
+        top: usize,
+        left: usize,
+    },
+    Tree(Tree),
+}
+
+impl View {
+    fn name(&self) -> &'static str {
+        match self {
+            View::Answer { .. } => "answer",
+            View::Picture { .. } => "picture",
+            View::Tree(_) => "tree",
+        }
+    }
+
+    fn handle(&mut self, key: Key, page: usize, width: usize) {
+        match self {
+            View::Answer { lines, top } => {
+                let mut menu = Menu {
+                    selected: *top,
+                    top: *top,
+                };
+                menu.handle(key, lines.len(), page);
+                *top = menu.selected;
+            }
+            View::Picture { picture, top, left } => {
+                let bottom = picture.height().saturating_sub(page);
+                let right = picture.width().saturating_sub(width);
+                match key {
+                    Key::Up => *top = top.saturating_sub(1),
+                    Key::Down => *top = (*top + 1).min(bottom),
+                    Key::PageUp => *top = top.saturating_sub(page),
+                    Key::PageDown => *top = (*top + page).min(bottom),
+                    Key::Left => *left = left.saturating_sub(4),
+                    Key::Right => *left = (*left + 4).min(right),
+                    Key::Home => (*top, *left) = (0, 0),
+                    Key::End => *top = bottom,
+                    _ => {}
+                }
+            }
+            View::Tree(tree) => tree.handle(key, page),
+        }
+    }
+
+    fn draw(&self, lines: &mut Vec<String>, page: usize, width: usize) {
+        match self {
+            View::Answer { lines: text, top } => {
+                lines.extend(text.iter().skip(*top).take(page).map(|x| fit(x, width)));
+            }
+            View::Picture { picture, top, left } => {
+                for row in (*top..picture.height()).take(page) {
+                    let mut line = String::new();
+                    let mut current: Option<Color> = None;
+                    for col in (*left..picture.width()).take(width) {
+                        let cell = picture.cells[(row, col)];
+                        if current != Some(cell.color) {
+                            let Color(r, g, b) = cell.color;
+                            let _ = write!(line, "\x1b[38;2;{r};{g};{b}m");
+                            current = Some(cell.color);
+                        }
+                        line.push(cell.glyph);
+                    }
+                    line.push_str("\x1b[0m");
+                    lines.push(line);
+                }
+            }
+            View::Tree(tree) => tree.draw(lines, page, width),
+        }
+    }
+}
+
+/// The result of running a part
+struct Output {
+    part: Part,
+    summary: String,
+    views: Vec<View>,
+    view: usize,
+}
+
+/// Which list or view the keys go to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Screen {
+    Days,
+    Inputs,
+    Output,
+}
+
+/// What a key asks of the event loop
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    None,
+    Quit,
+    Run(Part),
+}
+
+/// Everything on screen, kept apart from the terminal so it can be tested
+struct App {
+    solutions: Vec<Box<dyn DynSolution>>,
+    screen: Screen,
+    day: Menu,
+    inputs: Vec<String>,
+    input: Menu,
+    parsed: Option<Box<dyn Any>>,
+    output: Option<Output>,
+    /// Shown instead of the key help until the next key
+    status: Option<String>,
+    rows: usize,
+    columns: usize,
+}
+
+impl App {
+    fn new(solutions: Vec<Box<dyn DynSolution>>) -> Self {
+        App {
+            solutions,
+            screen: Screen::Days,
+            day: Menu::default(),
+            inputs: Vec::new(),
+            input: Menu::default(),
+            parsed: None,
+            output: None,
+            status: None,
+            rows: 24,
+            columns: 80,
+        }
+    }
+
+    fn solution(&self) -> &dyn DynSolution {
+        self.solutions[self.day.selected].as_ref()
+    }
+
+    /// Lines available between the title and the key help
+    fn page(&self) -> usize {
+        match self.screen {
+            // The summary and view names take two more
+            Screen::Output => self.rows.saturating_sub(4),
+            _ => self.rows.saturating_sub(2),
+        }
+        .max(1)
+    }
+
+    fn handle(&mut self, key: Key) -> Action {
+        self.status = None;
+        let page = self.page();
+        match (self.screen, key) {
+            // Ctrl-C doesn't interrupt in raw mode
+            (_, Key::Char('q' | '\x03')) | (Screen::Days, Key::Escape) => return Action::Quit,
+            (Screen::Days, Key::Enter) if !self.solutions.is_empty() => {
+                let day = self.solution().day();
+                self.inputs = common::input::variants(day);
+                self.input = Menu::default();
+                match self.inputs.is_empty() {
+                    true => {
+                        let dir = common::input::day_dir(day);
+                        self.status = Some(format!("No inputs in {}", dir.display()));
+                    }
+                    false => self.screen = Screen::Inputs,
+                }
+            }
+            (Screen::Days, _) => {
+                self.day.handle(key, self.solutions.len(), page);
+            }
+            (Screen::Inputs, Key::Enter) => {
+                let day = self.solution().day();
+                let variant = &self.inputs[self.input.selected];
+                match common::input::read(day, Some(variant)) {
+                    Ok(text) => self.open(&text),
+                    Err(e) => self.status = Some(e.to_string()),
+                }
+            }
+            (Screen::Inputs, Key::Escape) => self.screen = Screen::Days,
+            (Screen::Inputs, _) => {
+                self.input.handle(key, self.inputs.len(), page);
+            }
+            (Screen::Output, Key::Char('1')) => return Action::Run(Part::One),
+            (Screen::Output, Key::Char('2')) => return Action::Run(Part::Two),
+            (Screen::Output, Key::Escape) => {
+                self.screen = match self.inputs.is_empty() {
+                    true => Screen::Days,
+                    false => Screen::Inputs,
+                }
+            }
+            (Screen::Output, Key::Tab) => {
+                if let Some(output) = &mut self.output {
+                    output.view = (output.view + 1) % output.views.len();
+                }
+            }
+            (Screen::Output, _) => {
+                let width = self.columns;
+                if let Some(output) = &mut self.output {
+                    output.views[output.view].handle(key, page, width);
+                }
+            }
+        }
+        Action::None
+    }
+
+    /// Parse `text` for the selected day and wait for a part to run
+    fn open(&mut self, text: &str) {
+        match self.solution().parse(text) {
+            Ok(parsed) => {
+                self.parsed = Some(parsed);
+                self.output = None;
+                self.screen = Screen::Output;
+            }
+            Err(e) => self.status = Some(e.to_string()),
+        }
+    }
+
+    /// Solve `part` of the parsed input, gathering every view of it
+    fn run(&mut self, part: Part) {
+        let Some(parsed) = &self.parsed else {
+            return;
+        };
+        let solution = self.solution();
+        let start = Instant::now();
+        let answer = solution.solve(parsed.as_ref(), part);
+        let elapsed = start.elapsed();
+
+        let mut views: Vec<View> = Vec::new();
+        let summary = match answer {
+            Ok(answer) => {
+                views.push(View::Answer {
+                    lines: answer_lines(&answer, elapsed),
+                    top: 0,
+                });
+                if let Some(picture) = solution.picture(parsed.as_ref(), part) {
+                    views.push(View::Picture {
+                        picture,
+                        top: 0,
+                        left: 0,
+                    });
+                }
+                if let Some(outline) = solution.outline(parsed.as_ref(), part) {
+                    views.push(View::Tree(Tree::new(outline)));
+                }
+                match answer {
+                    Answer::Text(x) if x.contains('\n') => format!("Part {part}: see answer"),
+                    _ => format!("Part {part}: {answer}"),
+                }
+            }
+            Err(e) => {
+                views.push(View::Answer {
+                    lines: e.to_string().lines().map(str::to_owned).collect(),
+                    top: 0,
+                });
+                format!("Part {part} failed")
+            }
+        };
+        self.output = Some(Output {
+            part,
+            summary: format!("{summary} ({elapsed:.2?})"),
+            // The most detailed view is the most interesting
+            view: views.len() - 1,
+            views,
+        });
+    }
+
+    /// Exactly `self.rows` lines to show
+    fn draw(&self) -> Vec<String> {
+        let width = self.columns;
+        let page = self.page();
+        let mut title = String::from(" aoc explore");
+        if self.screen != Screen::Days {
+            let _ = write!(title, " / day {}", self.solution().day());
+        }
+        if self.screen == Screen::Output {
+            let _ = write!(title, " / {}", self.inputs[self.input.selected]);
+            if let Some(output) = &self.output {
+                let _ = write!(title, " / part {}", output.part);
+            }
+        }
+        let mut lines = vec![highlight(&fit(&format!("{title:width$}"), width), true)];
+
+        match self.screen {
+            Screen::Days => {
+                let names: Vec<String> = self
+                    .solutions
+                    .iter()
+                    .map(|x| format!("Day {}", x.day()))
+                    .collect();
+                draw_menu(&mut lines, &names, &self.day, page, width);
+            }
+            Screen::Inputs => draw_menu(&mut lines, &self.inputs, &self.input, page, width),
+            Screen::Output => match &self.output {
+                None => lines.push("Press 1 or 2 to run a part".to_owned()),
+                Some(output) => {
+                    lines.push(fit(&output.summary, width));
+                    let mut tabs = String::new();
+                    for (i, view) in output.views.iter().enumerate() {
+                        tabs += &highlight(&format!(" {} ", view.name()), i == output.view);
+                    }
+                    lines.push(tabs);
+                    output.views[output.view].draw(&mut lines, page, width);
+                }
+            },
+        }
+
+        lines.resize(self.rows.saturating_sub(1), String::new());
+        let help = match self.screen {
+            Screen::Days => "up/down: choose a day  enter: pick  q: quit",
+            Screen::Inputs => "up/down: choose an input  enter: parse  esc: back  q: quit",
+            Screen::Output => {
+                "1/2: run a part  tab: next view  arrows: move  enter: fold  esc: back  q: quit"
+            }
+        };
+        let status = self.status.as_deref().unwrap_or(help);
+        lines.push(format!("\x1b[2m{}\x1b[0m", fit(status, width)));
+        lines
+    }
+}
+
+/// The answer on its own lines, then how long it took
+fn answer_lines(answer: &Answer, elapsed: Duration) -> Vec<String> {
+    let mut lines: Vec<String> = answer.to_string().lines().map(str::to_owned).collect();
+    lines.push(String::new());
+    lines.push(format!("Solved in {elapsed:.2?}"));
+    lines
+}
+
+fn draw_menu(lines: &mut Vec<String>, names: &[String], menu: &Menu, page: usize, width: usize) {
+    let top = menu.top(page);
+    for (i, name) in names.iter().enumerate().skip(top).take(page) {
+        lines.push(highlight(
+            &fit(&format!("  {name}  "), width),
+            i == menu.selected,
+        ));
+    }
+}
+
+/// Cut `text` to `width` characters
+fn fit(text: &str, width: usize) -> String {
+    text.chars().take(width).collect()
+}
+
+/// Show `text` in reverse video when `selected`
+fn highlight(text: &str, selected: bool) -> String {
+    match selected {
+        true => format!("\x1b[7m{text}\x1b[0m"),
+        false => text.to_owned(),
+    }
+}
+
+/// The terminal in raw mode on the alternate screen, put back when dropped
+struct Terminal {
+    /// `stty -g` settings from before
+    saved: String,
+}
+
+impl Terminal {
+    fn enter() -> io::Result<Self> {
+        let saved = stty(&["-g"])?.trim().to_owned();
+        stty(&["raw", "-echo"])?;
+        let mut stdout = io::stdout();
+        // Alternate screen and hidden cursor
+        stdout.write_all(b"\x1b[?1049h\x1b[?25l")?;
+        stdout.flush()?;
+        Ok(Terminal { saved })
+    }
+
+    /// Rows and columns, or 24x80 if stty can't tell
+    fn size() -> (usize, usize) {
+        stty(&["size"])
+            .ok()
+            .and_then(|size| {
+                let (rows, columns) = size.trim().split_once(' ')?;
+                Some((rows.parse().ok()?, columns.parse().ok()?))
+            })
+            .filter(|&(rows, columns)| rows > 0 && columns > 0)
+            .unwrap_or((24, 80))
+    }
+
+    fn draw(&self, lines: &[String]) -> io::Result<()> {
+        // Raw mode needs an explicit carriage return before each newline
+        let mut screen = String::from("\x1b[H");
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                screen.push_str("\r\n");
+            }
+            screen.push_str(line);
+            screen.push_str("\x1b[0m\x1b[K");
+        }
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(screen.as_bytes())?;
+        stdout.flush()
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let _ = io::stdout().write_all(b"\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// Run `stty` on the terminal, returning what it prints
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("stty {} failed", args.join(" "))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Browse `solutions` until the user quits
+pub fn explore(solutions: Vec<Box<dyn DynSolution>>) -> Result<(), AocError> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(AocError::Semantic("explore needs a terminal".to_owned()));
+    }
+    let terminal = Terminal::enter()?;
+    let mut app = App::new(solutions);
+    let mut stdin = io::stdin().lock();
+    let mut buffer = [0u8; 64];
+    loop {
+        (app.rows, app.columns) = Terminal::size();
+        terminal.draw(&app.draw())?;
+        let count = stdin.read(&mut buffer)?;
+        if count == 0 {
+            return Ok(());
+        }
+        for key in decode_keys(&buffer[..count]) {
+            match app.handle(key) {
+                Action::None => {}
+                Action::Quit => return Ok(()),
+                Action::Run(part) => {
+                    app.status = Some(format!("Running part {part}..."));
+                    terminal.draw(&app.draw())?;
+                    app.run(part);
+                    app.status = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys() {
+        assert_eq!(
+            decode_keys(b"\x1b[A\x1b[B\x1bOC\x1b[5~\x1b[1;5Dq\r\x1b"),
+            vec![
+                Key::Up,
+                Key::Down,
+                Key::Right,
+                Key::PageUp,
+                Key::Left,
+                Key::Char('q'),
+                Key::Enter,
+                Key::Escape
+            ]
+        );
+        // Unknown sequences are skipped whole
+        assert_eq!(decode_keys(b"\x1b[15~1"), vec![Key::Char('1')]);
+    }
+
+    #[test]
+    fn tree_folding() {
+        let outline = Outline::new(
+            "root",
+            vec![
+                Outline::new("a", vec![Outline::leaf("a1"), Outline::leaf("a2")]),
+                Outline::leaf("b"),
+            ],
+        );
+        let mut tree = Tree::new(outline);
+        let labels = |tree: &Tree| -> Vec<String> {
+            tree.rows().iter().map(|x| x.node.label.clone()).collect()
+        };
+        assert_eq!(labels(&tree), ["root", "a", "b"]);
+
+        tree.handle(Key::Down, 10);
+        tree.handle(Key::Right, 10);
+        assert_eq!(labels(&tree), ["root", "a", "a1", "a2", "b"]);
+        // Right again steps into the children, left goes back to the parent
+        tree.handle(Key::Right, 10);
+        assert_eq!(tree.cursor.selected, 2);
+        tree.handle(Key::Left, 10);
+        assert_eq!(tree.cursor.selected, 1);
+        tree.handle(Key::Left, 10);
+        assert_eq!(labels(&tree), ["root", "a", "b"]);
+
+        tree.handle(Key::Home, 10);
+        tree.handle(Key::Enter, 10);
+        assert_eq!(labels(&tree), ["root"]);
+    }
+
+    #[test]
+    fn runs_a_part() {
+        let mut app = App::new(vec![common::register::<day16::Day16>()]);
+        app.inputs = vec!["test".to_owned()];
+        app.open("9C005AC2F8F0");
+        assert_eq!(app.screen, Screen::Output);
+        assert_eq!(app.handle(Key::Char('2')), Action::Run(Part::Two));
+        app.run(Part::Two);
+
+        let lines = app.draw();
+        assert_eq!(lines.len(), app.rows);
+        assert!(lines[0].contains("day 16 / test / part 2"));
+        assert!(lines[1].starts_with("Part 2: 0 ("));
+        assert_eq!(lines[3], highlight("- equal to v4 = 0", true));
+        assert_eq!(lines[4], "    literal v5 = 5");
+
+        // Back to the answer
+        app.handle(Key::Tab);
+        assert_eq!(app.draw()[3], "0");
+        app.handle(Key::Escape);
+        assert_eq!(app.screen, Screen::Inputs);
+        assert_eq!(app.handle(Key::Char('q')), Action::Quit);
+    }
+}
@@ -0,0 +1,418 @@
+//! Downloading puzzle inputs into the day directories.
+//!
+//! The session token is the `session` cookie from a logged in browser. It's
+//! read from `$AOC_SESSION`, or from a config file of `key = value` lines:
+//!
+//! ```text
+//! session = 53616c7465645f5f...
+//! # optional, these are the defaults
+//! base_url = https://adventofcode.com
+//! min_delay = 5
+//! ```
+//!
+//! The config file is `$AOC_CONFIG`, or `aoc/config` in `$XDG_CONFIG_HOME`
+//! (`~/.config` by default). `$AOC_BASE_URL` overrides the base URL.
+//!
+//! Plain `http://` URLs are fetched directly, `https://` ones through `curl`.
+//! An input that's already downloaded is never fetched again, and requests
+//! are spaced at least `min_delay` seconds apart, even across runs.
+
+use common::parse::{ParseError, Span};
+use common::{input, AocError};
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const YEAR: u16 = 2021;
+
+const SESSION_VAR: &str = "AOC_SESSION";
+const BASE_URL_VAR: &str = "AOC_BASE_URL";
+const CONFIG_VAR: &str = "AOC_CONFIG";
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_MIN_DELAY: Duration = Duration::from_secs(5);
+
+const USER_AGENT: &str = "aoc-2021 input fetcher (rust, std only)";
+
+/// Settings for fetching inputs, from the config file and environment
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub min_delay: Duration,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_owned(),
+            min_delay: DEFAULT_MIN_DELAY,
+        }
+    }
+}
+
+impl Config {
+    /// Read the settings from a config file's contents
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut config = Config::default();
+        for line in Span::new(text).lines() {
+            let line = line.trim();
+            if line.is_empty() || line.as_str().starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once("=")
+                .ok_or_else(|| line.error("expected key = value"))?;
+            let value = value.trim();
+            match key.trim().as_str() {
+                "session" => config.session = Some(value.as_str().to_owned()),
+                "base_url" => config.base_url = value.as_str().to_owned(),
+                "min_delay" => {
+                    // Negative, infinite or NaN seconds would panic
+                    config.min_delay = Duration::try_from_secs_f64(value.parse()?)
+                        .map_err(|_| value.error("expected a number of seconds, at least 0"))?;
+                }
+                _ => return Err(key.error("expected session, base_url or min_delay")),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Config file location, see the module docs
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os(CONFIG_VAR) {
+            return Some(PathBuf::from(path));
+        }
+        let dir = match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        };
+        Some(dir.join("aoc").join("config"))
+    }
+
+    /// The config file if there is one, with the environment on top
+    pub fn load() -> Result<Self, AocError> {
+        let mut config = match Config::path() {
+            Some(path) if path.is_file() => {
+                let text = fs::read_to_string(&path)?;
+                Config::parse(&text).map_err(|e| AocError::from(e).in_file(path.display()))?
+            }
+            _ => Config::default(),
+        };
+        if let Ok(session) = env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        if let Ok(base_url) = env::var(BASE_URL_VAR) {
+            config.base_url = base_url;
+        }
+        Ok(config)
+    }
+}
+
+/// What [Fetcher::fetch] did
+#[derive(Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already there
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    min_delay: Duration,
+    /// Holds the time of the last request, to space them out across runs.
+    /// File modification times are too coarse for short delays.
+    stamp: PathBuf,
+}
+
+impl Fetcher {
+    pub fn new(config: &Config, stamp: PathBuf) -> Result<Self, AocError> {
+        let session = config.session.as_deref().map(str::trim).unwrap_or("");
+        if session.is_empty() {
+            let place = match Config::path() {
+                Some(path) => format!("add `session = ...` to {}", path.display()),
+                None => "set $AOC_CONFIG".to_owned(),
+            };
+            return Err(AocError::Semantic(format!(
+                "no session token, set ${SESSION_VAR} or {place}"
+            )));
+        }
+        Ok(Fetcher {
+            base_url: config.base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            min_delay: config.min_delay,
+            stamp,
+        })
+    }
+
+    /// Fetcher for the configured day directories, see [input::input_root]
+    pub fn from_config(config: &Config) -> Result<Self, AocError> {
+        Fetcher::new(config, input::input_root().join(".aoc_fetch_stamp"))
+    }
+
+    pub fn url(&self, day: u8) -> String {
+        format!("{}/{YEAR}/day/{day}/input", self.base_url)
+    }
+
+    /// Download the input for `day` to `input.txt` in `dir` unless it's there
+    pub fn fetch(&self, day: u8, dir: &Path) -> Result<Fetched, AocError> {
+        let path = dir.join("input.txt");
+        if path.is_file() {
+            return Ok(Fetched::Cached(path));
+        }
+
+        self.wait();
+        let body = get(&self.url(day), &self.session);
+        fs::write(&self.stamp, now().as_nanos().to_string())?;
+        let body = body?;
+        if body.trim().is_empty() {
+            return Err(AocError::Semantic(format!(
+                "empty input from {}",
+                self.url(day)
+            )));
+        }
+
+        // Write then rename so a failure never leaves half an input cached
+        fs::create_dir_all(dir)?;
+        let partial = dir.join("input.txt.part");
+        fs::write(&partial, body)?;
+        fs::rename(&partial, &path)?;
+        Ok(Fetched::Downloaded(path))
+    }
+
+    /// Sleep until `min_delay` has passed since the last request
+    fn wait(&self) {
+        let since = fs::read_to_string(&self.stamp)
+            .ok()
+            .and_then(|x| x.trim().parse::<u64>().ok())
+            .and_then(|last| now().checked_sub(Duration::from_nanos(last)));
+        if let Some(since) = since {
+            if let Some(remaining) = self.min_delay.checked_sub(since) {
+                thread::sleep(remaining);
+            }
+        }
+    }
+}
+
+/// Time since the Unix epoch
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Body of a GET request to `url` with the session cookie
+fn get(url: &str, session: &str) -> io::Result<String> {
+    if let Some(rest) = url.strip_prefix("http://") {
+        http_get(rest, session)
+    } else if url.starts_with("https://") {
+        curl_get(url, session)
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{url}: expected an http:// or https:// URL"),
+        ))
+    }
+}
+
+/// Plain HTTP/1.0, so the body is never chunked and ends with the connection
+fn http_get(url: &str, session: &str) -> io::Result<String> {
+    let (host, path) = match url.find('/') {
+        Some(index) => url.split_at(index),
+        None => (url, "/"),
+    };
+    let address = match host.contains(':') {
+        true => host.to_owned(),
+        false => format!("{host}:80"),
+    };
+
+    let mut stream = TcpStream::connect(&address)?;
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    write!(
+        stream,
+        "GET {path} HTTP/1.0\r\nHost: {host}\r\nUser-Agent: {USER_AGENT}\r\n\
+         Cookie: session={session}\r\nConnection: close\r\n\r\n"
+    )?;
+    let mut response: Vec<u8> = Vec::new();
+    stream.read_to_end(&mut response)?;
+
+    let response = String::from_utf8(response)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "response isn't UTF-8"))?;
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidData, "response has no end of headers")
+    })?;
+    let status = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|x| x.parse::<u16>().ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "response has no status"))?;
+    match status {
+        200 => Ok(body.to_owned()),
+        _ => Err(status_error(status, &format!("http://{url}"))),
+    }
+}
+
+fn status_error(status: u16, url: &str) -> io::Error {
+    let hint = match status {
+        400 | 401 | 500 => ", is the session token still valid?",
+        404 => ", is the puzzle unlocked yet?",
+        _ => "",
+    };
+    io::Error::other(format!("HTTP {status} from {url}{hint}"))
+}
+
+/// Let curl deal with TLS. The cookie goes through stdin so the token doesn't
+/// show up in the process list.
+fn curl_get(url: &str, session: &str) -> io::Result<String> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--location"])
+        .args(["--user-agent", USER_AGENT, "--header", "@-"])
+        .args(["--write-out", "\n%{http_code}", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| io::Error::new(e.kind(), format!("running curl for https: {e}")))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        writeln!(stdin, "Cookie: session={session}")?;
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("curl {url}: {}", stderr.trim())));
+    }
+
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "response isn't UTF-8"))?;
+    let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+    match status.parse::<u16>() {
+        Ok(200) => Ok(body.to_owned()),
+        Ok(status) => Err(status_error(status, url)),
+        Err(_) => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("curl {url}: no status code"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::time::Instant;
+
+    /// Answer each connection with the next response, sending back the
+    /// requests received
+    fn stub_server(responses: Vec<&'static str>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request: Vec<u8> = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let count = stream.read(&mut buffer).unwrap();
+                    request.extend_from_slice(&buffer[..count]);
+                }
+                stream.write_all(response.as_bytes()).unwrap();
+                sender.send(String::from_utf8(request).unwrap()).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-fetch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn fetcher(base_url: &str, min_delay: Duration, dir: &Path) -> Fetcher {
+        let config = Config {
+            session: Some("secret".to_owned()),
+            base_url: base_url.to_owned(),
+            min_delay,
+        };
+        Fetcher::new(&config, dir.join("stamp")).unwrap()
+    }
+
+    #[test]
+    fn downloads_once_then_uses_the_cache() {
+        let (url, requests) = stub_server(vec!["HTTP/1.1 200 OK\r\n\r\n3,4,3,1,2\n"]);
+        let dir = temp_dir("cache");
+        let fetcher = fetcher(&url, Duration::ZERO, &dir);
+        let day_dir = dir.join("day6");
+
+        let path = day_dir.join("input.txt");
+        assert_eq!(
+            fetcher.fetch(6, &day_dir).unwrap(),
+            Fetched::Downloaded(path.clone())
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap(), "3,4,3,1,2\n");
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /2021/day/6/input HTTP/1.0\r\n"));
+        assert!(request.contains("\r\nCookie: session=secret\r\n"));
+
+        // The stub only answers once, so this must not connect
+        assert_eq!(fetcher.fetch(6, &day_dir).unwrap(), Fetched::Cached(path));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn errors_leave_nothing_cached() {
+        let (url, _requests) = stub_server(vec!["HTTP/1.1 404 Not Found\r\n\r\nNot yet"]);
+        let dir = temp_dir("error");
+        let fetcher = fetcher(&url, Duration::ZERO, &dir);
+
+        let error = fetcher.fetch(25, &dir).unwrap_err().to_string();
+        assert!(error.contains("HTTP 404"), "{error}");
+        assert!(!dir.join("input.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn spaces_out_requests() {
+        let ok = "HTTP/1.0 200 OK\r\n\r\n1\n";
+        let (url, _requests) = stub_server(vec![ok, ok]);
+        let dir = temp_dir("delay");
+        let delay = Duration::from_millis(200);
+        let fetcher = fetcher(&url, delay, &dir);
+
+        let start = Instant::now();
+        fetcher.fetch(1, &dir.join("day1")).unwrap();
+        fetcher.fetch(2, &dir.join("day2")).unwrap();
+        assert!(start.elapsed() >= delay);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reads_config() {
+        let config = Config::parse(
+            "# token\nsession = abc\n\nbase_url = http://localhost:8000/\nmin_delay = 0.5\n",
+        )
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("abc"));
+        assert_eq!(config.min_delay, Duration::from_millis(500));
+
+        let fetcher = Fetcher::new(&config, PathBuf::from("stamp")).unwrap();
+        assert_eq!(fetcher.url(3), "http://localhost:8000/2021/day/3/input");
+
+        assert_eq!(Config::parse("colour = red").unwrap_err().line, 1);
+        for delay in ["-1", "inf", "NaN", "1e300"] {
+            let error = Config::parse(&format!("min_delay = {delay}")).unwrap_err();
+            assert_eq!(error.column, 13, "{delay}");
+        }
+        assert!(Fetcher::new(&Config::default(), PathBuf::from("stamp")).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! Fuzz targets for every day's parser and day 16's packet decoder, run by
+//! `cargo test` through [common::fuzz].
+//!
+//! Each target gets arbitrary bytes and has to either parse them or return an
+//! error, and a day has to solve both parts of whatever it parses or return
+//! an error for them too. Inputs that failed before are kept in
+//! `fuzz/<target>/` at the top of the workspace and replayed on every run,
+//! next to the day's own inputs and a few generated ones that the mutations
+//! start from.
+
+use common::fuzz::{self, Mode};
+use common::input;
+use common::random::Rng;
+use common::Part;
+use std::path::PathBuf;
+
+use crate::days;
+
+/// Where a target's corpus is kept
+fn corpus(target: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../fuzz")
+        .join(target)
+}
+
+/// The day's inputs and small generated ones, to mutate
+fn seeds(day: u8) -> Vec<Vec<u8>> {
+    let solution = days::find(day).unwrap();
+    let mut seeds: Vec<Vec<u8>> = input::variants(day)
+        .iter()
+        .map(|variant| input::read(day, Some(variant)).unwrap().into_bytes())
+        .collect();
+    for seed in 0..3 {
+        seeds.extend(
+            solution
+                .generate(Some(3), &mut Rng::new(seed))
+                .map(String::into_bytes),
+        );
+    }
+    seeds
+}
+
+/// Fuzz `target` with `seeds`, failing the test on the first input it panics
+/// or hangs on
+fn fuzz(name: &str, seeds: &[Vec<u8>], target: impl Fn(&[u8]) + Send + 'static) {
+    let mode = Mode::from_env().unwrap_or_else(|e| panic!("{e}"));
+    if let Err(crash) = fuzz::run(&corpus(name), seeds, mode, target) {
+        panic!("fuzz target {name} failed on {crash}");
+    }
+}
+
+/// The day's parser, as `aoc run` calls it, then both parts on whatever it
+/// parses
+fn fuzz_day(day: u8) {
+    fuzz(&format!("day{day}"), &seeds(day), move |data| {
+        let solution = days::find(day).unwrap();
+        if let Ok(parsed) = solution.parse(&String::from_utf8_lossy(data)) {
+            for part in [Part::One, Part::Two] {
+                let _ = solution.solve(parsed.as_ref(), part);
+            }
+        }
+    });
+}
+
+macro_rules! day_targets {
+    ($($name:ident = $day:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                fuzz_day($day);
+            }
+        )*
+    };
+}
+
+day_targets!(
+    day1 = 1,
+    day2 = 2,
+    day3 = 3,
+    day4 = 4,
+    day5 = 5,
+    day6 = 6,
+    day7 = 7,
+    day8 = 8,
+    day9 = 9,
+    day10 = 10,
+    day11 = 11,
+    day12 = 12,
+    day13 = 13,
+    day14 = 14,
+    day15 = 15,
+    day16 = 16,
+    day17 = 17,
+);
+
+/// Packets decoded from hex, and from bits: the low bit of each byte, or the
+/// bytes as they are to try characters that aren't bits
+#[test]
+fn day16_packet() {
+    fuzz("day16_packet", &seeds(16), |data| {
+        let text = String::from_utf8_lossy(data);
+        if let Ok(packet) = day16::Packet::from_hex(&text) {
+            packet.version_sum();
+        }
+        let bits: String = data
+            .iter()
+            .map(|byte| if byte & 1 == 1 { '1' } else { '0' })
+            .collect();
+        let _ = day16::Packet::from_bin(&bits);
+        let _ = day16::Packet::from_bin(&text);
+    });
+}
@@ -0,0 +1,536 @@
+use common::random::Rng;
+use common::{AocError, DynSolution, Options, Part};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+mod bench;
+mod days;
+mod explore;
+mod fetch;
+#[cfg(test)]
+mod fuzz;
+mod regression;
+#[cfg(target_os = "linux")]
+mod watch;
+
+const USAGE: &str = "\
+Usage:
+    aoc run <day|all> [--part <1|2>] [--format <text|json>] [--render <file>]
+            [--animate <file|dir>] [--fps <n>] [-v|-vv] [--log <filter>]
+            [input]
+    aoc test [day|all] [variant] [--bless]
+    aoc bench <day|all> [-n <iterations>] [--output <file>]
+              [--baseline <file>] [--threshold <percent>] [input]
+    aoc fetch <day>... [--base-url <url>]
+    aoc explore
+    aoc watch <day> [input]
+    aoc gen <day> [--size <n>] [--seed <n>]
+
+The input is a file path, `-` for stdin, or the name of an input variant in
+the day's directory: `test` reads dayN/test_input.txt. Without one,
+dayN/input.txt is used. Day directories are looked up in $AOC_INPUT_DIR when
+it's set.
+
+`--format json` prints one JSON object per answer instead, with the day, part,
+answer, seconds elapsed and any extra details the day provides.
+
+`--render` draws the day's state after the last part it ran, in the format
+named by the file's extension: .txt, .ppm, .png or .svg. `-` draws it in the
+terminal in color. Days 5, 9, 11, 13, 15 and 17 have something to draw.
+
+`--animate` records a frame for each step of the last part's simulation, as
+an animated GIF for a .gif file, numbered PPM files in a directory otherwise,
+or played back in the terminal for `-`. `--fps` sets the speed, 10 frames per
+second by default. Days 6, 11, 13, 14 and 17 are simulations.
+
+`-v` logs what the solvers are doing to stderr, `-vv` in even more detail.
+`--log` picks levels per day instead, like `day16=trace,day11=debug`, with a
+bare level for the other days. $AOC_LOG sets a default filter the same way.
+
+`test` checks answers against the answers files next to the inputs, like
+dayN/test_answers.txt for the `test` variant. `--bless` records the current
+answers instead.
+
+`bench` times parsing and each part over several iterations (10 by default)
+and reports the min, median and max. `--output` saves the timings as tab
+separated values, and `--baseline` compares against a saved file, failing when
+a median is more than the threshold (10% by default) slower.
+
+`fetch` downloads each day's input to dayN/input.txt unless it's already
+there. The session token comes from $AOC_SESSION or `session = ...` in
+~/.config/aoc/config (or $AOC_CONFIG), which can also set `base_url` and
+`min_delay`, the seconds to wait between requests.
+
+`explore` opens a terminal UI to pick a day, an input and a part, and browse
+the answer along with the day's picture or its tree of results, like day 16's
+packets or day 12's paths.
+
+`watch` runs a day, then runs it again through `cargo run` whenever its
+source, its input or the input's answers file changes. Each run shows how the
+answers changed since the last one and whether they match the answers file.
+It needs Linux.
+
+`gen` prints a random input for the day, about as big as the real one or
+`--size` lines, boards, packets or whatever the day's input is made of. The
+same `--seed` (0 by default) always gives the same input.";
+
+/// Which days the command applies to
+enum Days {
+    All,
+    One(u8),
+}
+
+struct RunArgs {
+    days: Days,
+    parts: Vec<Part>,
+    options: Options,
+    input: Option<String>,
+}
+
+impl RunArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut days: Option<Days> = None;
+        let mut parts: Vec<Part> = Part::BOTH.to_vec();
+        let mut options = Options::default();
+        let mut input: Option<String> = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" | "-p" => {
+                    let number = args.next().ok_or("--part needs a value")?;
+                    let part = Part::from_number(number)
+                        .ok_or_else(|| format!("Invalid part {number:?}, expected 1 or 2"))?;
+                    parts = vec![part];
+                }
+                _ if options.parse_arg(arg, &mut args.by_ref().cloned())? => {}
+                "all" if days.is_none() => days = Some(Days::All),
+                _ if days.is_none() => match arg.parse::<u8>() {
+                    Ok(day) => days = Some(Days::One(day)),
+                    Err(_) => return Err(format!("Invalid day {arg:?}")),
+                },
+                _ if input.is_none() => input = Some(arg.to_owned()),
+                _ => return Err(format!("Unexpected argument {arg:?}")),
+            }
+        }
+
+        let days = days.ok_or("Missing day")?;
+        if matches!(days, Days::All) && (options.render.is_some() || options.animate.is_some()) {
+            return Err("--render and --animate need a single day".to_owned());
+        }
+        Ok(RunArgs {
+            days,
+            parts,
+            options,
+            input,
+        })
+    }
+}
+
+struct TestArgs {
+    days: Days,
+    variant: Option<String>,
+    bless: bool,
+}
+
+impl TestArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut days: Option<Days> = None;
+        let mut variant: Option<String> = None;
+        let mut bless = false;
+
+        for arg in args {
+            match arg.as_str() {
+                "--bless" => bless = true,
+                "all" if days.is_none() => days = Some(Days::All),
+                _ if days.is_none() && variant.is_none() => match arg.parse::<u8>() {
+                    Ok(day) => days = Some(Days::One(day)),
+                    Err(_) => variant = Some(arg.to_owned()),
+                },
+                _ if variant.is_none() => variant = Some(arg.to_owned()),
+                _ => return Err(format!("Unexpected argument {arg:?}")),
+            }
+        }
+
+        Ok(TestArgs {
+            days: days.unwrap_or(Days::All),
+            variant,
+            bless,
+        })
+    }
+}
+
+struct BenchArgs {
+    days: Days,
+    input: Option<String>,
+    iterations: usize,
+    output: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+impl BenchArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut days: Option<Days> = None;
+        let mut input: Option<String> = None;
+        let mut iterations: usize = 10;
+        let mut output: Option<PathBuf> = None;
+        let mut baseline: Option<PathBuf> = None;
+        let mut threshold: f64 = 10.0;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--iterations" | "-n" => {
+                    let number = value()?;
+                    iterations = match number.parse::<usize>() {
+                        Ok(x) if x > 0 => x,
+                        _ => return Err(format!("Invalid iterations {number:?}")),
+                    };
+                }
+                "--output" | "-o" => output = Some(PathBuf::from(value()?)),
+                "--baseline" => baseline = Some(PathBuf::from(value()?)),
+                "--threshold" => {
+                    let percent = value()?;
+                    threshold = percent
+                        .trim_end_matches('%')
+                        .parse()
+                        .map_err(|_| format!("Invalid threshold {percent:?}"))?;
+                }
+                "all" if days.is_none() => days = Some(Days::All),
+                _ if days.is_none() => match arg.parse::<u8>() {
+                    Ok(day) => days = Some(Days::One(day)),
+                    Err(_) => return Err(format!("Invalid day {arg:?}")),
+                },
+                _ if input.is_none() => input = Some(arg.to_owned()),
+                _ => return Err(format!("Unexpected argument {arg:?}")),
+            }
+        }
+
+        Ok(BenchArgs {
+            days: days.ok_or("Missing day")?,
+            input,
+            iterations,
+            output,
+            baseline,
+            threshold: threshold / 100.0,
+        })
+    }
+}
+
+struct WatchArgs {
+    day: u8,
+    input: Option<String>,
+}
+
+impl WatchArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let (day, input) = match args {
+            [day] => (day, None),
+            [day, input] => (day, Some(input.to_owned())),
+            [] => return Err("Missing day".to_owned()),
+            [_, _, arg, ..] => return Err(format!("Unexpected argument {arg:?}")),
+        };
+        let day = day
+            .parse::<u8>()
+            .map_err(|_| format!("Invalid day {day:?}"))?;
+        Ok(WatchArgs { day, input })
+    }
+}
+
+struct GenArgs {
+    day: u8,
+    size: Option<usize>,
+    seed: u64,
+}
+
+impl GenArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut day: Option<u8> = None;
+        let mut size: Option<usize> = None;
+        let mut seed: u64 = 0;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--size" | "-n" => {
+                    let number = value()?;
+                    size = Some(
+                        number
+                            .parse()
+                            .map_err(|_| format!("Invalid size {number:?}"))?,
+                    );
+                }
+                "--seed" => {
+                    let number = value()?;
+                    seed = number
+                        .parse()
+                        .map_err(|_| format!("Invalid seed {number:?}"))?;
+                }
+                _ if day.is_none() => match arg.parse::<u8>() {
+                    Ok(x) => day = Some(x),
+                    Err(_) => return Err(format!("Invalid day {arg:?}")),
+                },
+                _ => return Err(format!("Unexpected argument {arg:?}")),
+            }
+        }
+
+        Ok(GenArgs {
+            day: day.ok_or("Missing day")?,
+            size,
+            seed,
+        })
+    }
+}
+
+struct FetchArgs {
+    days: Vec<u8>,
+    base_url: Option<String>,
+}
+
+impl FetchArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut days: Vec<u8> = Vec::new();
+        let mut base_url: Option<String> = None;
+
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--base-url" => {
+                    base_url = Some(args.next().ok_or("--base-url needs a value")?.to_owned())
+                }
+                _ => match arg.parse::<u8>() {
+                    Ok(day @ 1..=25) => days.push(day),
+                    _ => return Err(format!("Invalid day {arg:?}")),
+                },
+            }
+        }
+
+        if days.is_empty() {
+            return Err("Missing day".to_owned());
+        }
+        Ok(FetchArgs { days, base_url })
+    }
+}
+
+fn run_day(solution: &dyn DynSolution, args: &RunArgs) -> Result<(), AocError> {
+    let source = common::input::resolve(solution.day(), args.input.as_deref())?;
+    let text = source.read()?;
+
+    let parsed = solution.parse(&text).map_err(|e| e.in_file(&source))?;
+    for &part in &args.parts {
+        common::print_part(solution, parsed.as_ref(), part, args.options.format)?;
+    }
+    let last = args.parts.last().copied().unwrap_or(Part::Two);
+    args.options
+        .render(|| solution.picture(parsed.as_ref(), last))?;
+    args.options
+        .animate(|recorder| solution.animate(parsed.as_ref(), last, recorder))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let args = RunArgs::parse(args)?;
+    args.options.init_logging()?;
+    match args.days {
+        Days::All => {
+            // Only a variant name makes sense for every day at once, so skip
+            // the days that don't have it
+            let mut failed: u32 = 0;
+            for solution in days::all() {
+                if let Err(e) = common::input::resolve(solution.day(), args.input.as_deref()) {
+                    eprintln!("Skipping day {}: {e}", solution.day());
+                    continue;
+                }
+                // Keep going so one bad input doesn't hide the other answers
+                if let Err(e) = run_day(solution.as_ref(), &args) {
+                    eprintln!("Day {}: {e}", solution.day());
+                    failed += 1;
+                }
+            }
+            if failed > 0 {
+                return Err(format!("{failed} day(s) failed"));
+            }
+        }
+        Days::One(day) => {
+            let solution = days::find(day).ok_or(format!("Day {day} isn't solved"))?;
+            run_day(solution.as_ref(), &args).map_err(|e| format!("Day {day}: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+fn test(args: &[String]) -> Result<(), String> {
+    let args = TestArgs::parse(args)?;
+    let solutions = match args.days {
+        Days::All => days::all(),
+        Days::One(day) => vec![days::find(day).ok_or(format!("Day {day} isn't solved"))?],
+    };
+
+    let mut failed: u32 = 0;
+    for solution in solutions {
+        let day = solution.day();
+        // Blessing records every input, checking only those with answers
+        let variants = match (&args.variant, args.bless) {
+            (Some(variant), _) => vec![variant.to_owned()],
+            (None, true) => common::input::variants(day),
+            (None, false) => regression::checked_variants(day),
+        };
+
+        for variant in variants {
+            if args.bless {
+                match regression::bless(solution.as_ref(), &variant) {
+                    Ok(path) => println!("Recorded {}", path.display()),
+                    Err(e) => {
+                        eprintln!("FAIL day {day} {variant}: {e}");
+                        failed += 1;
+                    }
+                }
+                continue;
+            }
+
+            match regression::check(solution.as_ref(), &variant) {
+                Ok(checks) => {
+                    for check in checks {
+                        println!("{check}");
+                        failed += !check.passed() as u32;
+                    }
+                }
+                Err(e) => {
+                    println!("FAIL day {day} {variant}: {e}");
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{failed} check(s) failed")),
+    }
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let args = BenchArgs::parse(args)?;
+    let solutions = match args.days {
+        Days::All => days::all(),
+        Days::One(day) => vec![days::find(day).ok_or(format!("Day {day} isn't solved"))?],
+    };
+    // Read the baseline first so a typo doesn't waste a long run
+    let baseline = match &args.baseline {
+        Some(path) => Some(bench::read_results(path).map_err(|e| e.to_string())?),
+        None => None,
+    };
+
+    let mut timings: Vec<bench::Timing> = Vec::new();
+    for solution in solutions {
+        let day = solution.day();
+        let source = match common::input::resolve(day, args.input.as_deref()) {
+            Ok(source) => source,
+            Err(e) => {
+                eprintln!("Skipping day {day}: {e}");
+                continue;
+            }
+        };
+        let result = source.read().map_err(AocError::from).and_then(|text| {
+            bench::bench_day(solution.as_ref(), &text, args.iterations)
+                .map_err(|e| e.in_file(&source))
+        });
+        match result {
+            Ok(day_timings) => {
+                for timing in &day_timings {
+                    println!("{timing}");
+                }
+                timings.extend(day_timings);
+            }
+            Err(e) => return Err(format!("Day {day}: {e}")),
+        }
+    }
+
+    if let Some(path) = &args.output {
+        std::fs::write(path, bench::format_results(&timings))
+            .map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+
+    if let Some(baseline) = baseline {
+        let regressions = bench::regressions(&timings, &baseline, args.threshold);
+        for regression in &regressions {
+            println!("SLOWER {regression}");
+        }
+        if !regressions.is_empty() {
+            return Err(format!(
+                "{} stage(s) more than {:.0}% slower than the baseline",
+                regressions.len(),
+                args.threshold * 100.0
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn fetch(args: &[String]) -> Result<(), String> {
+    let args = FetchArgs::parse(args)?;
+    let mut config = fetch::Config::load().map_err(|e| e.to_string())?;
+    if let Some(base_url) = args.base_url {
+        config.base_url = base_url;
+    }
+    let fetcher = fetch::Fetcher::from_config(&config).map_err(|e| e.to_string())?;
+
+    for day in args.days {
+        match fetcher.fetch(day, &common::input::day_dir(day)) {
+            Ok(fetch::Fetched::Cached(path)) => println!("Already have {}", path.display()),
+            Ok(fetch::Fetched::Downloaded(path)) => println!("Downloaded {}", path.display()),
+            Err(e) => return Err(format!("Day {day}: {e}")),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn watch(args: &[String]) -> Result<(), String> {
+    let args = WatchArgs::parse(args)?;
+    days::find(args.day).ok_or(format!("Day {} isn't solved", args.day))?;
+    watch::watch(args.day, args.input.as_deref())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn watch(args: &[String]) -> Result<(), String> {
+    WatchArgs::parse(args)?;
+    Err("watch needs Linux's inotify".to_owned())
+}
+
+fn gen(args: &[String]) -> Result<(), String> {
+    let args = GenArgs::parse(args)?;
+    let day = args.day;
+    let solution = days::find(day).ok_or(format!("Day {day} isn't solved"))?;
+    let input = solution
+        .generate(args.size, &mut Rng::new(args.seed))
+        .ok_or(format!("Day {day} has no input generator"))?;
+    print!("{input}");
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(|x| x.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("test") => test(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("fetch") => fetch(&args[1..]),
+        Some("watch") => watch(&args[1..]),
+        Some("gen") => gen(&args[1..]),
+        Some("explore") if args.len() == 1 => {
+            explore::explore(days::all()).map_err(|e| e.to_string())
+        }
+        _ => Err(USAGE.to_owned()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
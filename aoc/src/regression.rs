@@ -0,0 +1,225 @@
+//! Checking solvers against answers recorded next to their inputs.
+//!
+//! Each input variant can have an answers file in the day's directory,
+//! `test_input.txt` is checked against `test_answers.txt` and `input.txt`
+//! against `answers.txt`. The file lists the answer to each part:
+//!
+//! ```text
+//! 1: 1656
+//! 2: 195
+//! ```
+//!
+//! Answers that span several lines start on the line after the part number
+//! and run until the next part. Only the parts listed are checked.
+
+use common::parse::{ParseError, Span};
+use common::{input, Answer, AocError, DynSolution, Part};
+use std::fs;
+use std::path::PathBuf;
+
+/// Answers file for an input variant, `test` becomes `test_answers.txt`
+pub fn answers_path(day: u8, variant: &str) -> PathBuf {
+    let name = match variant {
+        input::DEFAULT_VARIANT => "answers.txt".to_owned(),
+        _ => format!("{variant}_answers.txt"),
+    };
+    input::day_dir(day).join(name)
+}
+
+/// Input variants of `day` that have an answers file
+pub fn checked_variants(day: u8) -> Vec<String> {
+    input::variants(day)
+        .into_iter()
+        .filter(|variant| answers_path(day, variant).is_file())
+        .collect()
+}
+
+/// Answers compare equal when they print the same, ignoring trailing spaces
+pub fn normalize(answer: &str) -> String {
+    let lines: Vec<&str> = answer.trim_end().lines().map(|x| x.trim_end()).collect();
+    lines.join("\n")
+}
+
+pub fn parse_answers(text: &str) -> Result<Vec<(Part, String)>, ParseError> {
+    let mut answers: Vec<(Part, String)> = Vec::new();
+    // Whether the last answer has a line yet, even a blank one, so the next
+    // line goes after a newline
+    let mut started = false;
+    for line in Span::new(text).lines() {
+        let part = line
+            .split_once(":")
+            .and_then(|(part, answer)| Some((Part::from_number(part.as_str())?, answer)));
+        match (part, answers.last_mut()) {
+            (Some((part, answer)), _) => {
+                let answer = answer.trim().to_string();
+                started = !answer.is_empty();
+                answers.push((part, answer));
+            }
+            // more lines of a multi-line answer
+            (None, Some((_, answer))) => {
+                if started {
+                    answer.push('\n');
+                }
+                answer.push_str(line.as_str());
+                started = true;
+            }
+            (None, None) => return Err(line.error("expected a part number like \"1:\"")),
+        }
+    }
+    Ok(answers
+        .into_iter()
+        .map(|(part, answer)| (part, normalize(&answer)))
+        .collect())
+}
+
+fn format_answers(answers: &[(Part, Answer)]) -> String {
+    let mut text = String::new();
+    for (part, answer) in answers {
+        let answer = normalize(&answer.to_string());
+        match answer.contains('\n') {
+            true => text.push_str(&format!("{part}:\n{answer}\n")),
+            false => text.push_str(&format!("{part}: {answer}\n")),
+        }
+    }
+    text
+}
+
+/// One part's answer compared with the recorded one
+pub struct Check {
+    pub day: u8,
+    pub variant: String,
+    pub part: Part,
+    pub expected: String,
+    pub actual: Result<Answer, AocError>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        match &self.actual {
+            Ok(answer) => normalize(&answer.to_string()) == self.expected,
+            Err(_) => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let name = format!("day {} {} part {}", self.day, self.variant, self.part);
+        match &self.actual {
+            _ if self.passed() => write!(f, "ok   {name}"),
+            Ok(answer) => write!(
+                f,
+                "FAIL {name}: expected {:?}, got {:?}",
+                self.expected,
+                normalize(&answer.to_string())
+            ),
+            Err(e) => write!(f, "FAIL {name}: {e}"),
+        }
+    }
+}
+
+/// Each part along with its answer, or why it couldn't be solved
+type Solved = Vec<(Part, Result<Answer, AocError>)>;
+
+/// Parse the input once and solve each of `parts`
+fn solve_variant(
+    solution: &dyn DynSolution,
+    variant: &str,
+    parts: &[Part],
+) -> Result<Solved, AocError> {
+    let source = input::resolve(solution.day(), Some(variant))?;
+    let text = source.read()?;
+    let parsed = solution.parse(&text).map_err(|e| e.in_file(&source))?;
+    Ok(parts
+        .iter()
+        .map(|&part| (part, solution.solve(parsed.as_ref(), part)))
+        .collect())
+}
+
+/// Compare the solver's answers for `variant` with its answers file
+pub fn check(solution: &dyn DynSolution, variant: &str) -> Result<Vec<Check>, AocError> {
+    let path = answers_path(solution.day(), variant);
+    let text = fs::read_to_string(&path)?;
+    let expected = parse_answers(&text).map_err(|e| AocError::from(e).in_file(path.display()))?;
+
+    let parts: Vec<Part> = expected.iter().map(|(part, _)| *part).collect();
+    let actual = solve_variant(solution, variant, &parts)?;
+    Ok(expected
+        .into_iter()
+        .zip(actual)
+        .map(|((part, expected), (_, actual))| Check {
+            day: solution.day(),
+            variant: variant.to_owned(),
+            part,
+            expected,
+            actual,
+        })
+        .collect())
+}
+
+/// Record the current answers for `variant`, returning the answers file.
+///
+/// Nothing is written unless both parts can be solved.
+pub fn bless(solution: &dyn DynSolution, variant: &str) -> Result<PathBuf, AocError> {
+    let mut answers: Vec<(Part, Answer)> = Vec::new();
+    for (part, answer) in solve_variant(solution, variant, &Part::BOTH)? {
+        answers.push((part, answer?));
+    }
+    let path = answers_path(solution.day(), variant);
+    fs::write(&path, format_answers(&answers))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn answers_format_round_trips() {
+        let answers = vec![
+            (Part::One, Answer::Number(16)),
+            (Part::Two, Answer::Text("#  #\n####  \n".to_owned())),
+        ];
+        let text = format_answers(&answers);
+        assert_eq!(text, "1: 16\n2:\n#  #\n####\n");
+        assert_eq!(
+            parse_answers(&text).unwrap(),
+            vec![
+                (Part::One, "16".to_owned()),
+                (Part::Two, "#  #\n####".to_owned())
+            ]
+        );
+        assert_eq!(parse_answers("\n1: 2").unwrap_err().line, 1);
+
+        // A blank first line is part of the answer
+        let answers = vec![(Part::Two, Answer::Text("\n# #\n###".to_owned()))];
+        let text = format_answers(&answers);
+        assert_eq!(text, "2:\n\n# #\n###\n");
+        assert_eq!(
+            parse_answers(&text).unwrap(),
+            vec![(Part::Two, "\n# #\n###".to_owned())]
+        );
+    }
+
+    /// Every recorded answer, run `aoc test --bless` to update them
+    #[test]
+    fn recorded_answers() {
+        let mut failures: Vec<String> = Vec::new();
+        let mut checked: usize = 0;
+        for solution in days::all() {
+            for variant in checked_variants(solution.day()) {
+                match check(solution.as_ref(), &variant) {
+                    Ok(checks) => {
+                        checked += checks.len();
+                        failures
+                            .extend(checks.iter().filter(|x| !x.passed()).map(|x| x.to_string()));
+                    }
+                    Err(e) => failures.push(format!("day {} {variant}: {e}", solution.day())),
+                }
+            }
+        }
+        assert!(checked > 0, "no answers files found");
+        assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+    }
+}
@@ -0,0 +1,420 @@
+//! `aoc watch`, re-running a day whenever its source or input changes.
+//!
+//! Changes are picked up with Linux's inotify, called directly since there
+//! are no dependencies to wrap it. Each run goes through `cargo run` so edits
+//! to the solver are rebuilt, and its answers are compared with the previous
+//! run and with the regression answers file when there is one.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fs::File;
+use std::io::{self, IsTerminal, Read};
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::os::raw::{c_char, c_int, c_short, c_ulong};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use common::{input, Part};
+
+use crate::regression;
+
+extern "C" {
+    fn inotify_init1(flags: c_int) -> c_int;
+    fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+    fn poll(fds: *mut PollFd, nfds: c_ulong, timeout: c_int) -> c_int;
+}
+
+/// `struct pollfd` from `poll.h`
+#[repr(C)]
+struct PollFd {
+    fd: c_int,
+    events: c_short,
+    revents: c_short,
+}
+
+const IN_CLOEXEC: c_int = 0o2000000;
+const IN_CLOSE_WRITE: u32 = 0x8;
+const IN_MOVED_TO: u32 = 0x80;
+const IN_DELETE: u32 = 0x200;
+const POLLIN: c_short = 0x1;
+
+/// Size of `struct inotify_event` before the name
+const EVENT_HEADER: usize = 16;
+
+/// An inotify instance watching directories for files being written, moved
+/// in or deleted
+pub struct Inotify {
+    file: File,
+    /// Directory of each watch descriptor
+    watches: HashMap<c_int, PathBuf>,
+}
+
+impl Inotify {
+    pub fn new() -> io::Result<Self> {
+        // SAFETY: no pointers are involved, the result is checked below
+        let fd = unsafe { inotify_init1(IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Inotify {
+            // SAFETY: the descriptor was just opened and nothing else owns it
+            file: unsafe { File::from_raw_fd(fd) },
+            watches: HashMap::new(),
+        })
+    }
+
+    /// Watch the files directly inside `dir`
+    pub fn add_watch(&mut self, dir: &Path) -> io::Result<()> {
+        let path = CString::new(dir.as_os_str().as_bytes())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let mask = IN_CLOSE_WRITE | IN_MOVED_TO | IN_DELETE;
+        // SAFETY: `path` is a valid C string that outlives the call
+        let wd = unsafe { inotify_add_watch(self.file.as_raw_fd(), path.as_ptr(), mask) };
+        if wd < 0 {
+            let e = io::Error::last_os_error();
+            return Err(io::Error::new(e.kind(), format!("{}: {e}", dir.display())));
+        }
+        self.watches.insert(wd, dir.to_owned());
+        Ok(())
+    }
+
+    /// Wait up to `timeout`, or forever for `None`, for files to change.
+    /// Returns the changed files, none if the time ran out.
+    pub fn wait(&mut self, timeout: Option<Duration>) -> io::Result<Vec<PathBuf>> {
+        let timeout = timeout.map_or(-1, |x| x.as_millis().min(c_int::MAX as u128) as c_int);
+        let mut fds = PollFd {
+            fd: self.file.as_raw_fd(),
+            events: POLLIN,
+            revents: 0,
+        };
+        loop {
+            // SAFETY: `fds` is one valid pollfd for the length of the call
+            match unsafe { poll(&mut fds, 1, timeout) } {
+                0 => return Ok(Vec::new()),
+                x if x > 0 => break,
+                _ => {
+                    let e = io::Error::last_os_error();
+                    if e.kind() != io::ErrorKind::Interrupted {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+
+        // Big enough for several events with the longest names
+        let mut buffer = vec![0u8; 16 * 1024];
+        let count = self.file.read(&mut buffer)?;
+        Ok(self.changed_files(&buffer[..count]))
+    }
+
+    /// Files named by the `struct inotify_event`s in `events`
+    fn changed_files(&self, mut events: &[u8]) -> Vec<PathBuf> {
+        let field = |bytes: &[u8], at: usize| -> [u8; 4] {
+            bytes[at..at + 4].try_into().expect("4 byte field")
+        };
+        let mut files: Vec<PathBuf> = Vec::new();
+        while events.len() >= EVENT_HEADER {
+            let wd = c_int::from_ne_bytes(field(events, 0));
+            let len = u32::from_ne_bytes(field(events, 12)) as usize;
+            let end = (EVENT_HEADER + len).min(events.len());
+            // The name is padded with NULs
+            let name = events[EVENT_HEADER..end].split(|&x| x == 0).next();
+            if let (Some(dir), Some(name)) = (self.watches.get(&wd), name) {
+                if !name.is_empty() {
+                    files.push(dir.join(std::ffi::OsStr::from_bytes(name)));
+                }
+            }
+            events = &events[end..];
+        }
+        files
+    }
+}
+
+/// Each part's answer as the `aoc run` prints it
+type Answers = Vec<(Part, String)>;
+
+/// Pick the answers out of `aoc run`'s text output, where multi-line answers
+/// start on the line after `Day N part P:`
+fn parse_output(day: u8, text: &str) -> Answers {
+    let mut answers: Answers = Vec::new();
+    for line in text.lines() {
+        let header = line
+            .strip_prefix(&format!("Day {day} part "))
+            .and_then(|x| x.split_once(':'))
+            .and_then(|(part, answer)| Some((Part::from_number(part)?, answer)));
+        match (header, answers.last_mut()) {
+            (Some((part, answer)), _) => answers.push((part, answer.trim().to_owned())),
+            (None, Some((_, answer))) => {
+                if !answer.is_empty() {
+                    answer.push('\n');
+                }
+                answer.push_str(line);
+            }
+            (None, None) => {}
+        }
+    }
+    answers
+        .into_iter()
+        .map(|(part, answer)| (part, regression::normalize(&answer)))
+        .collect()
+}
+
+/// ANSI color for `text`, when writing to a terminal
+fn paint(text: &str, color: u8, enabled: bool) -> String {
+    match enabled {
+        true => format!("\x1b[{color}m{text}\x1b[0m"),
+        false => text.to_owned(),
+    }
+}
+
+const RED: u8 = 31;
+const GREEN: u8 = 32;
+const YELLOW: u8 = 33;
+
+/// Each answer, marked with how it changed from `previous` and whether it
+/// matches `expected`
+fn report(
+    current: &Answers,
+    previous: Option<&Answers>,
+    expected: &Answers,
+    color: bool,
+) -> String {
+    let find = |answers: &Answers, part: Part| {
+        answers
+            .iter()
+            .find(|(x, _)| *x == part)
+            .map(|(_, answer)| answer.clone())
+    };
+    let mut text = String::new();
+    for (part, answer) in current {
+        let old = previous.and_then(|x| find(x, *part));
+        let changed = old.as_ref().is_some_and(|old| old != answer);
+        let mut status: Vec<String> = Vec::new();
+        match &old {
+            Some(old) if changed && !old.contains('\n') && !answer.contains('\n') => {
+                status.push(paint(&format!("was {old}"), YELLOW, color))
+            }
+            Some(_) if changed => status.push(paint("changed", YELLOW, color)),
+            Some(_) => status.push("unchanged".to_owned()),
+            None => {}
+        }
+        match find(expected, *part) {
+            Some(expected) if expected == *answer => status.push(paint("PASS", GREEN, color)),
+            Some(expected) if expected.contains('\n') => status.push(paint("FAIL", RED, color)),
+            Some(expected) => status.push(paint(&format!("FAIL, expected {expected}"), RED, color)),
+            None => {}
+        }
+        let status: String = status.iter().map(|x| format!("  {x}")).collect();
+
+        match answer.contains('\n') {
+            false => text.push_str(&format!("part {part}: {answer}{status}\n")),
+            true => {
+                text.push_str(&format!("part {part}:{status}\n"));
+                // Line by line against the old answer, so a changed letter
+                // stands out
+                let old: Vec<&str> = old.as_deref().unwrap_or(answer).lines().collect();
+                let new: Vec<&str> = answer.lines().collect();
+                for i in 0..old.len().max(new.len()) {
+                    match (old.get(i), new.get(i)) {
+                        (Some(a), Some(b)) if a == b => text.push_str(&format!("  {b}\n")),
+                        (a, b) => {
+                            if let Some(a) = a {
+                                text.push_str(&paint(&format!("- {a}"), RED, color));
+                                text.push('\n');
+                            }
+                            if let Some(b) = b {
+                                text.push_str(&paint(&format!("+ {b}"), GREEN, color));
+                                text.push('\n');
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    text
+}
+
+/// The workspace this binary was built from
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc is always inside the workspace")
+        .to_path_buf()
+}
+
+/// Build and run the day through cargo, leaving build errors on stderr
+fn run_day(day: u8, input: &Path) -> Result<Answers, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(["run", "--release", "--quiet", "-p", "aoc", "--", "run"])
+        .arg(day.to_string())
+        .arg(input)
+        .current_dir(workspace_root())
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Couldn't run cargo: {e}"))?;
+    match output.status.success() {
+        true => Ok(parse_output(day, &String::from_utf8_lossy(&output.stdout))),
+        false => Err(format!("Run failed ({})", output.status)),
+    }
+}
+
+/// Source directories of the day's crates, including any nested modules
+fn source_dirs(day: u8) -> Vec<PathBuf> {
+    let root = workspace_root();
+    let mut pending: Vec<PathBuf> = [format!("day{day}"), format!("day{day}_2")]
+        .iter()
+        .map(|name| root.join(name).join("src"))
+        .filter(|x| x.is_dir())
+        .collect();
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    while let Some(dir) = pending.pop() {
+        if let Ok(entries) = std::fs::read_dir(&dir) {
+            pending.extend(
+                entries
+                    .filter_map(|x| x.ok())
+                    .map(|x| x.path())
+                    .filter(|x| x.is_dir()),
+            );
+        }
+        dirs.push(dir);
+    }
+    dirs
+}
+
+/// Re-run `day` whenever its source, its input or the input's answers file
+/// changes, until interrupted
+pub fn watch(day: u8, arg: Option<&str>) -> Result<(), String> {
+    let input = match input::resolve(day, arg).map_err(|e| e.to_string())? {
+        input::InputSource::File(path) => path,
+        input::InputSource::Stdin => return Err("watch can't read stdin".to_owned()),
+    };
+    // Only variants have answers files
+    let variant = arg.unwrap_or(input::DEFAULT_VARIANT);
+    let answers = (!Path::new(variant).is_file()).then(|| regression::answers_path(day, variant));
+
+    let sources = source_dirs(day);
+    if sources.is_empty() {
+        return Err(format!(
+            "No source for day {day} in {}",
+            workspace_root().display()
+        ));
+    }
+    let mut inotify = Inotify::new().map_err(|e| format!("inotify: {e}"))?;
+    let mut dirs: Vec<&Path> = sources.iter().map(|x| x.as_path()).collect();
+    dirs.extend(input.parent());
+    dirs.extend(answers.as_ref().and_then(|x| x.parent()));
+    dirs.sort();
+    dirs.dedup();
+    for dir in dirs {
+        inotify.add_watch(dir).map_err(|e| e.to_string())?;
+    }
+    let relevant = |path: &PathBuf| {
+        *path == input
+            || Some(path) == answers.as_ref()
+            || (path.extension().is_some_and(|x| x == "rs")
+                && sources.iter().any(|x| path.parent() == Some(x.as_path())))
+    };
+
+    let color = io::stdout().is_terminal();
+    let mut previous: Option<Answers> = None;
+    let mut changed: Vec<PathBuf> = Vec::new();
+    loop {
+        let start = Instant::now();
+        println!("Running day {day} on {}", input.display());
+        match run_day(day, &input) {
+            Ok(current) => {
+                let expected = answers
+                    .as_ref()
+                    .and_then(|x| std::fs::read_to_string(x).ok())
+                    .and_then(|x| regression::parse_answers(&x).ok())
+                    .unwrap_or_default();
+                print!("{}", report(&current, previous.as_ref(), &expected, color));
+                previous = Some(current);
+            }
+            // Keep the last good answers to compare the next run with
+            Err(e) => println!("{}", paint(&e, RED, color)),
+        }
+        println!("Took {:.1?}, watching for changes", start.elapsed());
+
+        changed.clear();
+        while changed.is_empty() {
+            let files = inotify.wait(None).map_err(|e| e.to_string())?;
+            changed.extend(files.into_iter().filter(relevant));
+        }
+        // Editors and formatters tend to write a few times in a row
+        loop {
+            let files = inotify
+                .wait(Some(Duration::from_millis(200)))
+                .map_err(|e| e.to_string())?;
+            if files.is_empty() {
+                break;
+            }
+            changed.extend(files.into_iter().filter(relevant));
+        }
+        changed.sort();
+        changed.dedup();
+        let root = workspace_root();
+        let names: Vec<String> = changed
+            .iter()
+            .map(|x| x.strip_prefix(&root).unwrap_or(x).display().to_string())
+            .collect();
+        println!("\nChanged {}", names.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_run_output() {
+        let text = "Day 13 part 1: 17\nDay 13 part 2:\n#####\n#   #\n";
+        assert_eq!(
+            parse_output(13, text),
+            vec![
+                (Part::One, "17".to_owned()),
+                (Part::Two, "#####\n#   #".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_changes() {
+        let previous = vec![
+            (Part::One, "5".to_owned()),
+            (Part::Two, "#.\n.#".to_owned()),
+        ];
+        let current = vec![
+            (Part::One, "6".to_owned()),
+            (Part::Two, "#.\n##".to_owned()),
+        ];
+        let expected = vec![(Part::One, "6".to_owned())];
+        assert_eq!(
+            report(&current, Some(&previous), &expected, false),
+            "part 1: 6  was 5  PASS\npart 2:  changed\n  #.\n- .#\n+ ##\n"
+        );
+        assert_eq!(
+            report(&previous, None, &expected, false),
+            "part 1: 5  FAIL, expected 6\npart 2:\n  #.\n  .#\n"
+        );
+    }
+
+    #[test]
+    fn sees_files_change() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut inotify = Inotify::new().unwrap();
+        inotify.add_watch(&dir).unwrap();
+        assert!(inotify.wait(Some(Duration::ZERO)).unwrap().is_empty());
+
+        std::fs::write(dir.join("input.txt"), "1\n").unwrap();
+        let changed = inotify.wait(Some(Duration::from_secs(5))).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(changed, vec![dir.join("input.txt")]);
+    }
+}
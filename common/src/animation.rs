@@ -0,0 +1,399 @@
+//! Recording simulations one step at a time.
+//!
+//! Simulations call [Recorder::frame] once per step. Frames are drawn only
+//! when something is recording, and go straight to where they're exported
+//! rather than being kept, since some days draw hundreds of large frames. A
+//! [Gif] encodes them as they arrive; the command line can also write them as
+//! numbered PPM files or play them back in the terminal.
+
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::render::{Color, Picture};
+
+/// Where a simulation sends a picture of each step
+pub trait Recorder {
+    /// Record one step. `draw` is only called if frames are being kept.
+    fn frame(&mut self, draw: &dyn Fn() -> Picture);
+}
+
+/// Doesn't record anything, for running a simulation just for its answer
+impl Recorder for () {
+    fn frame(&mut self, _draw: &dyn Fn() -> Picture) {}
+}
+
+/// Keeps every frame in memory
+impl Recorder for Vec<Picture> {
+    fn frame(&mut self, draw: &dyn Fn() -> Picture) {
+        self.push(draw());
+    }
+}
+
+/// Animated GIF that loops forever.
+///
+/// Each frame only stores the rectangle that changed since the one before,
+/// with its own palette. Frames with more than 256 colors are reduced to 3
+/// bits of red and green and 2 of blue.
+#[derive(Clone, Debug)]
+pub struct Gif {
+    /// Hundredths of a second each frame is shown for
+    delay: u16,
+    /// Pixels per cell, picked from the first frame when `None`
+    cell_size: Option<usize>,
+    /// The image so far, which grows to fit the largest frame
+    canvas: Vec<Color>,
+    width: usize,
+    height: usize,
+    /// Encoded frames
+    body: Vec<u8>,
+}
+
+impl Gif {
+    /// Show each frame for `delay` hundredths of a second
+    pub fn new(delay: u16) -> Self {
+        Gif {
+            delay,
+            cell_size: None,
+            canvas: Vec::new(),
+            width: 0,
+            height: 0,
+            body: Vec::new(),
+        }
+    }
+
+    /// Pixels per cell rather than picking it from the first frame
+    pub fn with_cell_size(mut self, cell_size: usize) -> Self {
+        self.cell_size = Some(cell_size.max(1));
+        self
+    }
+
+    /// The contents of the GIF file
+    pub fn finish(self) -> Vec<u8> {
+        let mut file = b"GIF89a".to_vec();
+        file.extend((self.width as u16).to_le_bytes());
+        file.extend((self.height as u16).to_le_bytes());
+        // No global color table, every frame has its own
+        file.extend([0, 0, 0]);
+        // Loop forever
+        file.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+        file.extend(self.body);
+        file.push(0x3b);
+        file
+    }
+
+    fn add(&mut self, picture: &Picture) {
+        let size = *self.cell_size.get_or_insert_with(|| picture.cell_size());
+        let (picture_width, picture_height) = (picture.width() * size, picture.height() * size);
+        let (width, height) = (
+            self.width.max(picture_width),
+            self.height.max(picture_height),
+        );
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // Anything the picture doesn't cover is its background
+        let mut canvas = vec![picture.background; width * height];
+        for (y, row) in canvas.chunks_mut(width).take(picture_height).enumerate() {
+            for (x, pixel) in row.iter_mut().take(picture_width).enumerate() {
+                *pixel = picture.cells[(y / size, x / size)].color;
+            }
+        }
+
+        let (left, top, right, bottom) = if (width, height) == (self.width, self.height) {
+            changed_area(&self.canvas, &canvas, width)
+        } else {
+            (0, 0, width, height)
+        };
+        self.canvas = canvas;
+        self.width = width;
+        self.height = height;
+
+        let mut area = Vec::with_capacity((right - left) * (bottom - top));
+        for row in self.canvas.chunks(width).take(bottom).skip(top) {
+            area.extend_from_slice(&row[left..right]);
+        }
+        self.encode(&area, (left, top, right - left, bottom - top));
+    }
+
+    /// Append a frame showing `pixels` at `(left, top, width, height)`
+    fn encode(
+        &mut self,
+        pixels: &[Color],
+        (left, top, width, height): (usize, usize, usize, usize),
+    ) {
+        let (palette, indices) = index_colors(pixels);
+        let table_bits = (usize::BITS - (palette.len() - 1).leading_zeros()).max(1);
+
+        // Graphic control: leave the frame in place for the next one to
+        // draw over, and wait before showing it
+        self.body.extend([0x21, 0xf9, 0x04, 0x04]);
+        self.body.extend(self.delay.to_le_bytes());
+        self.body.extend([0x00, 0x00]);
+
+        self.body.push(0x2c);
+        for value in [left, top, width, height] {
+            self.body.extend((value as u16).to_le_bytes());
+        }
+        self.body.push(0x80 | (table_bits as u8 - 1));
+        for index in 0..1 << table_bits {
+            let Color(r, g, b) = palette.get(index).copied().unwrap_or(Color::BLACK);
+            self.body.extend([r, g, b]);
+        }
+
+        let min_code_size = table_bits.max(2) as u8;
+        self.body.push(min_code_size);
+        for block in lzw(&indices, min_code_size).chunks(255) {
+            self.body.push(block.len() as u8);
+            self.body.extend_from_slice(block);
+        }
+        self.body.push(0);
+    }
+}
+
+impl Recorder for Gif {
+    fn frame(&mut self, draw: &dyn Fn() -> Picture) {
+        self.add(&draw());
+    }
+}
+
+/// `(left, top, right, bottom)` bounding the pixels that differ, or a single
+/// pixel if none do since a frame can't be empty
+fn changed_area(before: &[Color], after: &[Color], width: usize) -> (usize, usize, usize, usize) {
+    let mut area: Option<(usize, usize, usize, usize)> = None;
+    for (index, _) in before
+        .iter()
+        .zip(after)
+        .enumerate()
+        .filter(|(_, (a, b))| a != b)
+    {
+        let (x, y) = (index % width, index / width);
+        area = Some(match area {
+            None => (x, y, x + 1, y + 1),
+            Some((left, top, right, bottom)) => {
+                (left.min(x), top.min(y), right.max(x + 1), bottom.max(y + 1))
+            }
+        });
+    }
+    area.unwrap_or((0, 0, 1, 1))
+}
+
+/// The distinct colors and each pixel's index into them, with the colors
+/// reduced to fit if there are more than 256
+fn index_colors(pixels: &[Color]) -> (Vec<Color>, Vec<u8>) {
+    let key = |Color(r, g, b): Color| (r as u32) << 16 | (g as u32) << 8 | b as u32;
+    let mut indices: BTreeMap<u32, u8> = BTreeMap::new();
+    let mut palette: Vec<Color> = Vec::new();
+    let mut reduce = false;
+    for &color in pixels {
+        if indices.contains_key(&key(color)) {
+            continue;
+        }
+        if palette.len() == 256 {
+            reduce = true;
+            break;
+        }
+        indices.insert(key(color), palette.len() as u8);
+        palette.push(color);
+    }
+
+    if reduce {
+        // Index bits are rrrgggbb, repeated to fill each channel so white
+        // stays white
+        let widen = |bits: u8| bits << 5 | bits << 2 | bits >> 1;
+        let palette = (0..=255u8)
+            .map(|x| Color(widen(x >> 5), widen(x >> 2 & 7), (x & 3) * 0x55))
+            .collect();
+        let indices = pixels
+            .iter()
+            .map(|&Color(r, g, b)| r & 0xe0 | (g >> 3) & 0x1c | b >> 6)
+            .collect();
+        return (palette, indices);
+    }
+    let pixels = pixels.iter().map(|&color| indices[&key(color)]).collect();
+    (palette, pixels)
+}
+
+/// GIF's variant of LZW compression, packing codes from the least
+/// significant bit
+fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODES: usize = 4096;
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let symbols = clear as usize;
+
+    let mut bytes: Vec<u8> = Vec::new();
+    let (mut bits, mut bit_count) = (0u32, 0u32);
+    let mut put = |code: u16, size: u32| {
+        bits |= (code as u32) << bit_count;
+        bit_count += size;
+        while bit_count >= 8 {
+            bytes.push(bits as u8);
+            bits >>= 8;
+            bit_count -= 8;
+        }
+    };
+
+    // The code for each string followed by each symbol, 0 for none since no
+    // string can have code 0
+    let mut table = vec![0u16; MAX_CODES * symbols];
+    let mut code_size = min_code_size as u32 + 1;
+    let mut next_code = end + 1;
+    put(clear, code_size);
+
+    let Some((&first, rest)) = indices.split_first() else {
+        put(end, code_size);
+        return finish_bits(bytes, bits, bit_count);
+    };
+    let mut prefix = first as u16;
+    for &symbol in rest {
+        let slot = prefix as usize * symbols + symbol as usize;
+        if table[slot] != 0 {
+            prefix = table[slot];
+            continue;
+        }
+        put(prefix, code_size);
+        if (next_code as usize) < MAX_CODES {
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            table[slot] = next_code;
+            next_code += 1;
+        } else {
+            put(clear, code_size);
+            table.fill(0);
+            code_size = min_code_size as u32 + 1;
+            next_code = end + 1;
+        }
+        prefix = symbol as u16;
+    }
+    put(prefix, code_size);
+    put(end, code_size);
+    finish_bits(bytes, bits, bit_count)
+}
+
+/// Flush the bits left over after the last whole byte
+fn finish_bits(mut bytes: Vec<u8>, bits: u32, bit_count: u32) -> Vec<u8> {
+    if bit_count > 0 {
+        bytes.push(bits as u8);
+    }
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Cell;
+
+    /// Decode a GIF LZW stream, to check [lzw] against
+    fn unlzw(bytes: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let mut table: Vec<Vec<u8>> = Vec::new();
+        let reset = |table: &mut Vec<Vec<u8>>| {
+            *table = (0..clear + 2).map(|x| vec![x as u8]).collect();
+        };
+        reset(&mut table);
+        let mut code_size = min_code_size as u32 + 1;
+        let (mut position, mut output) = (0usize, Vec::new());
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = (0..code_size)
+                .map(|bit| {
+                    (bytes[(position + bit as usize) / 8] >> ((position + bit as usize) % 8) & 1)
+                        as usize
+                })
+                .enumerate()
+                .fold(0, |acc, (bit, x)| acc | x << bit);
+            position += code_size as usize;
+            if code == clear {
+                reset(&mut table);
+                code_size = min_code_size as u32 + 1;
+                previous = None;
+                continue;
+            }
+            if code == clear + 1 {
+                return output;
+            }
+            let entry = match (table.get(code), &previous) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(previous)) => {
+                    let mut entry = previous.clone();
+                    entry.push(previous[0]);
+                    entry
+                }
+                (None, None) => panic!("code {code} before any string"),
+            };
+            if let Some(mut previous) = previous.take() {
+                previous.push(entry[0]);
+                table.push(previous);
+                if table.len() == 1 << code_size && code_size < 12 {
+                    code_size += 1;
+                }
+            }
+            output.extend_from_slice(&entry);
+            previous = Some(entry);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        let cases: [Vec<u8>; 4] = [
+            vec![],
+            vec![1; 10],
+            b"TOBEORNOTTOBEORTOBEORNOT".iter().map(|x| x % 4).collect(),
+            // Enough variety to fill the table and clear it
+            (0..20000u32).map(|x| (x * x / 7 % 251) as u8).collect(),
+        ];
+        for indices in cases {
+            let min_code_size = match indices.iter().max() {
+                Some(&max) if max >= 4 => 8,
+                _ => 2,
+            };
+            assert_eq!(unlzw(&lzw(&indices, min_code_size), min_code_size), indices);
+        }
+    }
+
+    #[test]
+    fn gif_frames_only_store_changes() {
+        let blank = Cell::new('.', Color::BLACK);
+        let mut first = Picture::new(4, 3, blank);
+        first.cells[(0, 0)] = Cell::new('#', Color::WHITE);
+        let mut second = first.clone();
+        second.cells[(2, 1)] = Cell::new('#', Color::RED);
+        second.cells[(1, 2)] = Cell::new('#', Color::RED);
+
+        let mut gif = Gif::new(10).with_cell_size(1);
+        gif.frame(&|| first.clone());
+        gif.frame(&|| second.clone());
+        gif.frame(&|| second.clone());
+        let file = gif.finish();
+
+        assert!(file.starts_with(b"GIF89a\x04\x00\x03\x00"));
+        assert!(file.ends_with(b"\x3b"));
+        let descriptors: Vec<&[u8]> = file
+            .windows(10)
+            .filter(|x| x[0] == 0x2c && x[9] & 0x80 != 0)
+            .map(|x| &x[1..9])
+            .collect();
+        assert_eq!(
+            descriptors,
+            [
+                &[0, 0, 0, 0, 4, 0, 3, 0][..],
+                &[1, 0, 1, 0, 2, 0, 2, 0],
+                &[0, 0, 0, 0, 1, 0, 1, 0],
+            ]
+        );
+    }
+
+    #[test]
+    fn many_colors_are_reduced() {
+        let pixels: Vec<Color> = (0..300u32)
+            .map(|x| Color(x as u8, (x / 2) as u8, 255))
+            .collect();
+        let (palette, indices) = index_colors(&pixels);
+        assert_eq!(palette.len(), 256);
+        assert_eq!(palette[indices[299] as usize], Color(36, 146, 255));
+        assert_eq!(palette[255], Color::WHITE);
+    }
+}
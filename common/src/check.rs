@@ -0,0 +1,245 @@
+//! Property tests, for checking that something holds over many random
+//! inputs rather than a few examples.
+//!
+//! [differential] runs two implementations of the same thing, usually a
+//! naive one and an optimised one, on each input and fails when they
+//! disagree. When an input fails it's shrunk, trying simpler and simpler
+//! versions of it through [Shrink] until none of them fail, so the failure
+//! is reported with the smallest input that shows it.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::random::Rng;
+
+/// A value that can be swapped for simpler ones when looking for the
+/// smallest input that fails
+pub trait Shrink: Sized {
+    /// Simpler values to try in place of this one, most promising first.
+    /// Each has to be strictly simpler, so shrinking comes to an end.
+    fn shrink(&self) -> Vec<Self>;
+}
+
+macro_rules! shrink_integer {
+    ($($t:ty),*) => {
+        $(
+            /// Towards 0: straight to 0, then halfway, then one closer
+            impl Shrink for $t {
+                #[allow(unused_comparisons)]
+                fn shrink(&self) -> Vec<Self> {
+                    let x = *self;
+                    let closer = if x < 0 { x + 1 } else { x.saturating_sub(1) };
+                    let mut candidates: Vec<Self> = Vec::from([0, x / 2, closer]);
+                    candidates.dedup();
+                    candidates.retain(|&candidate| candidate != x);
+                    candidates
+                }
+            }
+        )*
+    };
+}
+
+shrink_integer!(u8, u32, u64, usize, i32, i64);
+
+/// Shorter vectors first, dropping either half and then each item, then the
+/// items shrunk one at a time
+impl<T: Shrink + Clone> Shrink for Vec<T> {
+    fn shrink(&self) -> Vec<Self> {
+        let mut candidates: Vec<Self> = Vec::new();
+        let half = self.len() / 2;
+        if half > 0 {
+            candidates.push(self[half..].to_vec());
+            candidates.push(self[..half].to_vec());
+        }
+        for i in 0..self.len() {
+            let mut shorter = self.clone();
+            shorter.remove(i);
+            candidates.push(shorter);
+        }
+        for (i, item) in self.iter().enumerate() {
+            for simpler in item.shrink() {
+                let mut candidate = self.clone();
+                candidate[i] = simpler;
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+/// Dropping chars only, since shrinking a char rarely keeps an input valid
+impl Shrink for String {
+    fn shrink(&self) -> Vec<Self> {
+        let chars: Vec<char> = self.chars().collect();
+        (0..chars.len())
+            .map(|i| chars[..i].iter().chain(&chars[i + 1..]).collect())
+            .collect()
+    }
+}
+
+/// The first value shrunk, then the second
+impl<A: Shrink + Clone, B: Shrink + Clone> Shrink for (A, B) {
+    fn shrink(&self) -> Vec<Self> {
+        let first = self.0.shrink().into_iter().map(|a| (a, self.1.clone()));
+        let second = self.1.shrink().into_iter().map(|b| (self.0.clone(), b));
+        first.chain(second).collect()
+    }
+}
+
+/// An input that failed a property, shrunk as far as it would go
+#[derive(Clone, Debug)]
+pub struct Failure<T> {
+    /// Seed of the run and which of its inputs failed, to reproduce it
+    pub seed: u64,
+    pub case: u32,
+    /// The input that was generated
+    pub original: T,
+    /// The simplest input that still fails
+    pub minimal: T,
+    /// Why the minimal input fails
+    pub message: String,
+    /// How many times the input was shrunk
+    pub shrinks: u32,
+}
+
+impl<T: fmt::Debug> fmt::Display for Failure<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "property failed for case {} of seed {}",
+            self.case, self.seed
+        )?;
+        writeln!(f, "  minimal input: {:?}", self.minimal)?;
+        writeln!(f, "  {}", self.message)?;
+        write!(
+            f,
+            "  (shrunk {} times from {:?})",
+            self.shrinks, self.original
+        )
+    }
+}
+
+/// Check `property` on `cases` inputs made by `generate`, returning the
+/// first one that fails, shrunk
+pub fn find_failure<T: Shrink + Clone>(
+    cases: u32,
+    seed: u64,
+    mut generate: impl FnMut(&mut Rng) -> T,
+    mut property: impl FnMut(&T) -> Result<(), String>,
+) -> Option<Failure<T>> {
+    let mut rng = Rng::new(seed);
+    for case in 0..cases {
+        let original = generate(&mut rng);
+        let Err(message) = property(&original) else {
+            continue;
+        };
+
+        // Take the first simpler input that still fails until none do
+        let mut failure = Failure {
+            seed,
+            case,
+            minimal: original.clone(),
+            original,
+            message,
+            shrinks: 0,
+        };
+        'shrinking: loop {
+            for candidate in failure.minimal.shrink() {
+                if let Err(message) = property(&candidate) {
+                    failure.minimal = candidate;
+                    failure.message = message;
+                    failure.shrinks += 1;
+                    continue 'shrinking;
+                }
+            }
+            return Some(failure);
+        }
+    }
+    None
+}
+
+/// [find_failure], panicking with the shrunk input when there is one
+pub fn check<T: Shrink + Clone + fmt::Debug>(
+    cases: u32,
+    seed: u64,
+    generate: impl FnMut(&mut Rng) -> T,
+    property: impl FnMut(&T) -> Result<(), String>,
+) {
+    if let Some(failure) = find_failure(cases, seed, generate, property) {
+        panic!("{failure}");
+    }
+}
+
+/// Check that `naive` and `optimised` give the same result for `cases`
+/// inputs made by `generate`, panicking with the smallest input they
+/// disagree on
+pub fn differential<T, R>(
+    cases: u32,
+    seed: u64,
+    generate: impl FnMut(&mut Rng) -> T,
+    naive: impl Fn(&T) -> R,
+    optimised: impl Fn(&T) -> R,
+) where
+    T: Shrink + Clone + fmt::Debug,
+    R: PartialEq + fmt::Debug,
+{
+    check(cases, seed, generate, |input| {
+        match (naive(input), optimised(input)) {
+            (expected, actual) if expected == actual => Ok(()),
+            (expected, actual) => Err(format!("naive gave {expected:?}, optimised {actual:?}")),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shrinks_to_the_smallest_failure() {
+        // Any vector summing to 100 or more fails, and greedy shrinking has to
+        // stop right at 100
+        let failure = find_failure(
+            100,
+            1,
+            |rng| {
+                (0..rng.range(0..=10))
+                    .map(|_| rng.range(0u32..=60))
+                    .collect()
+            },
+            |numbers: &Vec<u32>| match numbers.iter().sum::<u32>() {
+                sum if sum < 100 => Ok(()),
+                sum => Err(format!("sum is {sum}")),
+            },
+        )
+        .unwrap();
+        assert_eq!(failure.minimal.iter().sum::<u32>(), 100);
+        assert_eq!(failure.message, "sum is 100");
+        assert!(failure.original.iter().sum::<u32>() >= 100);
+
+        let failure = find_failure(
+            100,
+            1,
+            |rng| (rng.range(0u32..=1000), rng.range(0u32..=1000)),
+            |&(a, b)| match a.min(17) + b.min(17) {
+                34 => Err("both at least 17".to_owned()),
+                _ => Ok(()),
+            },
+        )
+        .unwrap();
+        assert_eq!(failure.minimal, (17, 17));
+
+        assert!(find_failure(100, 1, |rng| rng.range(0u8..=9), |_| Ok(())).is_none());
+        assert_eq!(5u32.shrink(), [0, 2, 4]);
+        assert_eq!((-5i32).shrink(), [0, -2, -4]);
+        assert_eq!("abc".to_owned().shrink(), ["bc", "ac", "ab"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "naive gave 17, optimised 16")]
+    fn reports_disagreements() {
+        differential(100, 1, |rng| rng.range(0u32..=100), |&x| x, |&x| x.min(16));
+    }
+}
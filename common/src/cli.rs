@@ -0,0 +1,393 @@
+//! Printing answers and the command line of each day's binary.
+
+use std::any::Any;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use crate::animation::{Gif, Recorder};
+use crate::log::{self, Filter, Level};
+use crate::render::{self, Ascii, Picture, Ppm, Render};
+use crate::{answer_record, input, register, Answer, AocError, DynSolution, Json, Part, Solution};
+
+/// How answers are printed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// `Day 4 part 1: 11774`
+    #[default]
+    Text,
+    /// One JSON object per line with the day, part, answer, seconds elapsed
+    /// and any [Solution::extras]
+    Json,
+}
+
+impl Format {
+    /// Parse the value given to `--format`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "text" => Some(Format::Text),
+            "json" => Some(Format::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Options of a day's binary besides the input
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub format: Format,
+    /// Where `--render` should write a picture of the final state
+    pub render: Option<PathBuf>,
+    /// Where `--animate` should send a frame for each step
+    pub animate: Option<PathBuf>,
+    /// Frames per second for `--animate`
+    pub fps: f64,
+    /// How many times `-v` was given
+    pub verbose: u8,
+    /// `--log` filter like `day16=trace`, see [log]
+    pub log: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            format: Format::Text,
+            render: None,
+            animate: None,
+            fps: 10.0,
+            verbose: 0,
+            log: None,
+        }
+    }
+}
+
+impl Options {
+    /// Take `arg` if it's one of the options, along with its value from
+    /// `args`. Returns false for any other argument.
+    pub fn parse_arg(
+        &mut self,
+        arg: &str,
+        args: &mut dyn Iterator<Item = String>,
+    ) -> Result<bool, String> {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg {
+            "--format" => {
+                let name = value()?;
+                self.format = Format::from_name(&name)
+                    .ok_or_else(|| format!("Invalid format {name:?}, expected text or json"))?;
+            }
+            "--render" => self.render = Some(PathBuf::from(value()?)),
+            "--animate" => self.animate = Some(PathBuf::from(value()?)),
+            "-v" | "--verbose" => self.verbose = self.verbose.saturating_add(1),
+            "-vv" => self.verbose = self.verbose.saturating_add(2),
+            "--log" => {
+                let spec = value()?;
+                Filter::new(Level::Info).parse(&spec)?;
+                self.log = Some(spec);
+            }
+            "--fps" => {
+                let fps = value()?;
+                self.fps = match fps.parse::<f64>() {
+                    Ok(x) if x > 0.0 && x.is_finite() => x,
+                    _ => return Err(format!("Invalid frames per second {fps:?}")),
+                };
+            }
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Print log messages to stderr at the level asked for by `-v`, then
+    /// `$AOC_LOG` and `--log`
+    pub fn init_logging(&self) -> Result<(), String> {
+        let mut filter = Filter::new(Level::from_verbosity(self.verbose));
+        if let Ok(spec) = std::env::var(LOG_VAR) {
+            filter = filter
+                .parse(&spec)
+                .map_err(|e| format!("${LOG_VAR}: {e}"))?;
+        }
+        if let Some(spec) = &self.log {
+            filter = filter.parse(spec)?;
+        }
+        log::init(filter);
+        Ok(())
+    }
+
+    /// Write the picture made by `picture` if `--render` asked for one.
+    /// Errors if it did but the day has nothing to draw.
+    pub fn render(&self, picture: impl FnOnce() -> Option<Picture>) -> Result<(), AocError> {
+        let Some(target) = &self.render else {
+            return Ok(());
+        };
+        let picture =
+            picture().ok_or_else(|| AocError::Semantic("nothing to render".to_owned()))?;
+        write_picture(target, &picture)
+    }
+
+    /// Record `animate`'s frames if `--animate` asked for them: to the
+    /// terminal for `-`, as a GIF for a `.gif` file, otherwise as numbered
+    /// PPM files in a directory. `animate` returns false if the day has
+    /// nothing to animate.
+    pub fn animate(&self, animate: impl FnOnce(&mut dyn Recorder) -> bool) -> Result<(), AocError> {
+        let Some(target) = &self.animate else {
+            return Ok(());
+        };
+        let delay = Duration::from_secs_f64(1.0 / self.fps);
+        let animated = if target == Path::new("-") {
+            let mut playback = Playback::new(delay);
+            let animated = animate(&mut playback);
+            playback.finish()?;
+            animated
+        } else if target
+            .extension()
+            .is_some_and(|x| x.eq_ignore_ascii_case("gif"))
+        {
+            // GIF delays are in hundredths of a second, and most viewers
+            // slow down anything under 2
+            let mut gif = Gif::new((delay.as_millis() / 10).clamp(2, u16::MAX as u128) as u16);
+            let animated = animate(&mut gif);
+            if animated {
+                write_file(target, &gif.finish())?;
+            }
+            animated
+        } else {
+            let mut frames = PpmFrames::new(target);
+            let animated = animate(&mut frames);
+            frames.finish()?;
+            animated
+        };
+        match animated {
+            true => Ok(()),
+            false => Err(AocError::Semantic("nothing to animate".to_owned())),
+        }
+    }
+}
+
+/// Environment variable with a default `--log` filter
+pub const LOG_VAR: &str = "AOC_LOG";
+
+/// Writes each frame to its own PPM file in a directory, numbered from 0
+struct PpmFrames {
+    directory: PathBuf,
+    count: usize,
+    /// Picked from the first frame so they're all drawn at the same scale
+    cell_size: Option<usize>,
+    /// The first error, after which frames are skipped
+    error: Option<io::Error>,
+}
+
+impl PpmFrames {
+    fn new(directory: &Path) -> Self {
+        PpmFrames {
+            directory: directory.to_owned(),
+            count: 0,
+            cell_size: None,
+            error: None,
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Recorder for PpmFrames {
+    fn frame(&mut self, draw: &dyn Fn() -> Picture) {
+        if self.error.is_some() {
+            return;
+        }
+        let picture = draw();
+        let cell_size = *self.cell_size.get_or_insert_with(|| picture.cell_size());
+        let path = self.directory.join(format!("frame_{:05}.ppm", self.count));
+        let result = std::fs::create_dir_all(&self.directory)
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", self.directory.display())))
+            .and_then(|_| {
+                write_file(
+                    &path,
+                    &Ppm {
+                        cell_size: Some(cell_size),
+                    }
+                    .render(&picture),
+                )
+            });
+        self.error = result.err();
+        self.count += 1;
+    }
+}
+
+/// Draws each frame over the last one in the terminal
+struct Playback {
+    delay: Duration,
+    count: usize,
+    error: Option<io::Error>,
+}
+
+impl Playback {
+    fn new(delay: Duration) -> Self {
+        Playback {
+            delay,
+            count: 0,
+            error: None,
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Recorder for Playback {
+    fn frame(&mut self, draw: &dyn Fn() -> Picture) {
+        if self.error.is_some() {
+            return;
+        }
+        let start = Instant::now();
+        let text = Ascii { color: true }.render(&draw());
+        self.count += 1;
+        let mut stdout = io::stdout().lock();
+        // Move to the top left and clear the screen before drawing
+        let result = stdout
+            .write_all(b"\x1b[H\x1b[2J")
+            .and_then(|_| stdout.write_all(&text))
+            .and_then(|_| writeln!(stdout, "Frame {}", self.count))
+            .and_then(|_| stdout.flush());
+        self.error = result.err();
+        std::thread::sleep(self.delay.saturating_sub(start.elapsed()));
+    }
+}
+
+/// Write a file, naming it in any error
+fn write_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    std::fs::write(path, contents)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))
+}
+
+/// Write `picture` in the format named by the file's extension, or to the
+/// terminal in color when `target` is `-`
+pub fn write_picture(target: &Path, picture: &Picture) -> Result<(), AocError> {
+    if target == Path::new("-") {
+        io::stdout().write_all(&Ascii { color: true }.render(picture))?;
+        return Ok(());
+    }
+    let backend = target
+        .to_str()
+        .and_then(render::for_file_name)
+        .ok_or_else(|| {
+            AocError::Semantic(format!(
+                "can't render to {}, expected a .txt, .ppm, .png or .svg file",
+                target.display()
+            ))
+        })?;
+    write_file(target, &backend.render(picture))?;
+    Ok(())
+}
+
+/// Print an answer, starting multi-line answers on their own line
+pub fn print_answer(day: u8, part: Part, answer: &Answer) {
+    match answer {
+        Answer::Text(text) if text.contains('\n') => println!("Day {day} part {part}:\n{text}"),
+        _ => println!("Day {day} part {part}: {answer}"),
+    }
+}
+
+/// Print the `--format json` record for an answer
+pub fn print_record(day: u8, part: Part, answer: &Answer, elapsed: Duration, extras: Option<Json>) {
+    println!("{}", answer_record(day, part, answer, elapsed, extras));
+}
+
+/// Solve one part and print it in `format`
+pub fn print_part(
+    solution: &dyn DynSolution,
+    parsed: &dyn Any,
+    part: Part,
+    format: Format,
+) -> Result<(), AocError> {
+    let day = solution.day();
+    let start = Instant::now();
+    let answer = solution.solve(parsed, part)?;
+    let elapsed = start.elapsed();
+    match format {
+        Format::Text => print_answer(day, part, &answer),
+        Format::Json => print_record(day, part, &answer, elapsed, solution.extras(parsed, part)),
+    }
+    Ok(())
+}
+
+/// Parse the input and print the answer to each part, then render or animate
+/// part 2 if asked
+pub fn print_answers<S>(input: &str, options: &Options) -> Result<(), AocError>
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    let solution = register::<S>();
+    let parsed = solution.parse(input)?;
+    for part in Part::BOTH {
+        print_part(solution.as_ref(), parsed.as_ref(), part, options.format)?;
+    }
+    options.render(|| solution.picture(parsed.as_ref(), Part::Two))?;
+    options.animate(|recorder| solution.animate(parsed.as_ref(), Part::Two, recorder))
+}
+
+/// Body of a day's own binary.
+///
+/// Reads the input named on the command line (see [input](crate::input)) and
+/// hands it to `f` along with the [Options] asked for. Errors are printed and
+/// turned into a failing exit code.
+pub fn run_main(day: u8, f: impl FnOnce(&str, &Options) -> Result<(), AocError>) -> ExitCode {
+    run_main_with_args(day, std::env::args().skip(1), f)
+}
+
+/// [run_main] with the arguments given rather than the command line's, for
+/// a day that takes some of its own out first
+pub fn run_main_with_args(
+    day: u8,
+    args: impl IntoIterator<Item = String>,
+    f: impl FnOnce(&str, &Options) -> Result<(), AocError>,
+) -> ExitCode {
+    let (arg, options) = match main_args(args).and_then(|(arg, options)| {
+        options.init_logging()?;
+        Ok((arg, options))
+    }) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let result = input::resolve(day, arg.as_deref())
+        .map_err(AocError::from)
+        .and_then(|source| {
+            let text = source.read()?;
+            f(&text, &options).map_err(|e| e.in_file(&source))
+        });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Day {day}: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The input argument and options of a day's binary
+fn main_args(args: impl IntoIterator<Item = String>) -> Result<(Option<String>, Options), String> {
+    let mut input: Option<String> = None;
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            _ if options.parse_arg(&arg, &mut args)? => {}
+            _ if input.is_none() => input = Some(arg),
+            _ => return Err(format!("Unexpected argument {arg:?}")),
+        }
+    }
+    Ok((input, options))
+}
@@ -0,0 +1,104 @@
+use alloc::string::{String, ToString};
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io;
+
+use crate::parse::ParseError;
+
+/// Everything that can go wrong while solving a day.
+///
+/// Matches need a catch-all arm, since `Io` is only there with the `std`
+/// feature.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum AocError {
+    /// The input couldn't be found or read
+    #[cfg(feature = "std")]
+    Io(io::Error),
+
+    /// The input isn't in the expected format
+    Parse {
+        /// Where the input came from, when known
+        file: Option<String>,
+        error: ParseError,
+    },
+
+    /// The input parsed, but doesn't make sense for the puzzle. For example a
+    /// day 16 comparison packet without exactly two subpackets.
+    Semantic(String),
+}
+
+impl AocError {
+    /// Note the file a parse error came from, other errors are unchanged
+    pub fn in_file(self, file: impl fmt::Display) -> Self {
+        match self {
+            AocError::Parse { file: None, error } => AocError::Parse {
+                file: Some(file.to_string()),
+                error,
+            },
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "std")]
+            AocError::Io(e) => write!(f, "{e}"),
+            AocError::Parse {
+                file: Some(file),
+                error,
+            } => write!(
+                f,
+                "{file}:{}:{}: {}, found {:?}",
+                error.line, error.column, error.message, error.text
+            ),
+            AocError::Parse { file: None, error } => write!(f, "{error}"),
+            AocError::Semantic(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl core::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            AocError::Io(e) => Some(e),
+            AocError::Parse { error, .. } => Some(error),
+            AocError::Semantic(_) => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        AocError::Io(e)
+    }
+}
+
+impl From<ParseError> for AocError {
+    fn from(error: ParseError) -> Self {
+        AocError::Parse { file: None, error }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::csv_numbers;
+
+    #[test]
+    fn parse_errors_name_the_file() {
+        let error: AocError = csv_numbers::<u32>("1,2\n,x").unwrap_err().into();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 2: expected u32 (invalid digit found in string), found \"x\""
+        );
+        assert_eq!(
+            error.in_file("day6/input.txt").to_string(),
+            "day6/input.txt:2:2: expected u32 (invalid digit found in string), found \"x\""
+        );
+    }
+}
@@ -0,0 +1,457 @@
+//! Fuzzing, feeding parsers mangled inputs to check that they return an error
+//! instead of panicking or hanging.
+//!
+//! A target takes its input as bytes. [run] first replays every file in the
+//! target's corpus directory along with the seed inputs it's given, then
+//! mutates them: flipping bits, dropping, repeating and splicing chunks, and
+//! dropping in the numbers and separators parsers tend to trip over. Each
+//! input runs on a worker thread with a time limit, so a hang shows up as a
+//! failure rather than a stuck test. Workers have the default stack size, so
+//! an input nested deep enough to overflow a solver's stack takes the test
+//! down just like it would `aoc run`.
+//!
+//! How much fuzzing a run does is picked by `$AOC_FUZZ`, see [Mode::from_env].
+//! Inputs that fail while fuzzing on purpose are saved to the corpus, so
+//! they're replayed by every `cargo test` from then on.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::Once;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::random::Rng;
+
+/// Environment variable picking the [Mode]
+pub const FUZZ_VAR: &str = "AOC_FUZZ";
+
+/// Mutated inputs tried by a plain `cargo test`
+pub const QUICK_INPUTS: u32 = 300;
+
+/// Longest input a mutation makes
+const MAX_LEN: usize = 8192;
+
+/// How long one input may take before it counts as a hang
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Pieces of puzzle inputs that make for interesting mutations: numbers too
+/// big for their type, negative ones, and the separators days split on
+const DICTIONARY: &[&[u8]] = &[
+    b"0",
+    b"1",
+    b"9",
+    b"-1",
+    b"255",
+    b"65536",
+    b"4294967296",
+    b"18446744073709551616",
+    b"99999999999999999999999",
+    b",",
+    b" ",
+    b"\n",
+    b"\n\n",
+    b"\r\n",
+    b"-",
+    b" -> ",
+    b" | ",
+    b"=",
+    b"..",
+    b"x=",
+    b"y=",
+    b"0000",
+    b"FFFF",
+    b"start",
+    b"end",
+];
+
+/// How much fuzzing to do
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// Only run the corpus and seed inputs
+    Replay,
+    /// Run the corpus and seeds, then `inputs` mutations of them from `seed`,
+    /// saving failing inputs to the corpus when `save` is set
+    Mutate { inputs: u32, seed: u64, save: bool },
+}
+
+impl Mode {
+    /// The mode `$AOC_FUZZ` asks for: unset for a quick run that's the same
+    /// every time, `replay` for just the corpus, or a number of inputs for a
+    /// long run from a new seed that saves what it finds
+    pub fn from_env() -> Result<Self, String> {
+        let value = match std::env::var(FUZZ_VAR) {
+            Ok(value) => value,
+            Err(_) => {
+                return Ok(Mode::Mutate {
+                    inputs: QUICK_INPUTS,
+                    seed: 0,
+                    save: false,
+                })
+            }
+        };
+        match value.trim() {
+            "replay" => Ok(Mode::Replay),
+            inputs => match inputs.parse() {
+                Ok(inputs) => Ok(Mode::Mutate {
+                    inputs,
+                    seed: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |time| time.as_nanos() as u64),
+                    save: true,
+                }),
+                Err(_) => Err(format!(
+                    "${FUZZ_VAR} should be `replay` or a number of inputs, not {value:?}"
+                )),
+            },
+        }
+    }
+}
+
+/// What went wrong with an input
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Problem {
+    /// The panic message and where it came from
+    Panic(String),
+    /// Still running after this long
+    Hang(Duration),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Problem::Panic(message) => write!(f, "{message}"),
+            Problem::Hang(time) => write!(f, "still running after {time:?}"),
+        }
+    }
+}
+
+/// An input the target failed on
+#[derive(Clone, Debug)]
+pub struct Crash {
+    /// Made smaller when the target panics, as long as it still panics
+    pub input: Vec<u8>,
+    pub problem: Problem,
+    /// Seed of the mutations, when it wasn't a corpus or seed input
+    pub seed: Option<u64>,
+    /// Where the input was saved in the corpus
+    pub saved: Option<PathBuf>,
+}
+
+impl fmt::Display for Crash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const SHOWN: usize = 400;
+        let shown = &self.input[..self.input.len().min(SHOWN)];
+        write!(f, "input b\"{}\"", shown.escape_ascii())?;
+        if self.input.len() > SHOWN {
+            write!(f, "... ({} bytes)", self.input.len())?;
+        }
+        write!(f, "\n  {}", self.problem)?;
+        if let Some(seed) = self.seed {
+            write!(f, "\n  (mutated with seed {seed})")?;
+        }
+        if let Some(path) = &self.saved {
+            write!(f, "\n  saved to {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    /// Set on fuzz workers, whose panics are expected and reported as a
+    /// [Crash] instead of printed
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+    /// Message of the last panic on this thread, with its location
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Keep panics on fuzz workers to ourselves, leaving every other thread's
+/// printed as usual
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if QUIET.get() {
+                LAST_PANIC.set(Some(info.to_string()));
+            } else {
+                default(info);
+            }
+        }));
+    });
+}
+
+/// Run `target` on one input, catching a panic
+fn attempt(target: &impl Fn(&[u8]), input: &[u8]) -> Result<(), Problem> {
+    panic::catch_unwind(AssertUnwindSafe(|| target(input))).map_err(|_| {
+        let message = LAST_PANIC.take();
+        Problem::Panic(message.unwrap_or_else(|| "panicked".to_owned()))
+    })
+}
+
+/// A thread running the target on one input at a time.
+///
+/// A worker that hangs is left behind when the test ends, there's no stopping
+/// a thread from outside, so after a hang it takes no more inputs.
+struct Worker {
+    inputs: mpsc::Sender<Vec<u8>>,
+    results: mpsc::Receiver<Result<(), Problem>>,
+    hung: bool,
+}
+
+impl Worker {
+    fn start<F: Fn(&[u8]) + Send + 'static>(target: F) -> Self {
+        let (inputs, input_rx) = mpsc::channel::<Vec<u8>>();
+        let (result_tx, results) = mpsc::channel();
+        thread::Builder::new()
+            .name("fuzz".to_owned())
+            .spawn(move || {
+                QUIET.set(true);
+                for input in input_rx {
+                    if result_tx.send(attempt(&target, &input)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("can't start a fuzz worker");
+        Worker {
+            inputs,
+            results,
+            hung: false,
+        }
+    }
+
+    fn attempt(&mut self, input: &[u8]) -> Result<(), Problem> {
+        if self.hung {
+            return Err(Problem::Hang(TIMEOUT));
+        }
+        self.inputs
+            .send(input.to_vec())
+            .expect("fuzz worker stopped taking inputs");
+        match self.results.recv_timeout(TIMEOUT) {
+            Ok(result) => result,
+            Err(_) => {
+                self.hung = true;
+                Err(Problem::Hang(TIMEOUT))
+            }
+        }
+    }
+
+    /// Make a panicking input smaller by dropping ever smaller chunks of it,
+    /// as long as it keeps panicking
+    fn minimize(&mut self, input: &mut Vec<u8>, problem: &mut Problem) {
+        let mut chunk = input.len() / 2;
+        while chunk > 0 && !self.hung {
+            let mut start = 0;
+            while start + chunk <= input.len() {
+                let mut smaller = input.clone();
+                smaller.drain(start..start + chunk);
+                match self.attempt(&smaller) {
+                    Err(smaller_problem @ Problem::Panic(_)) => {
+                        *input = smaller;
+                        *problem = smaller_problem;
+                    }
+                    _ => start += chunk,
+                }
+            }
+            chunk /= 2;
+        }
+    }
+}
+
+/// Change `input` in one random way, `pool` has the other inputs to splice
+/// from
+fn mutate(input: &mut Vec<u8>, pool: &[Vec<u8>], rng: &mut Rng) {
+    let len = input.len();
+    let at = rng.range(0..=len);
+    let end = rng.range(at..=len);
+    match rng.below(7) {
+        0 if len > 0 => input[at.min(len - 1)] ^= 1 << rng.below(8),
+        1 if len > 0 => input[at.min(len - 1)] = rng.below(256) as u8,
+        2 => {
+            let word = rng.choose(DICTIONARY).unwrap();
+            input.splice(at..at, word.iter().copied());
+        }
+        3 => {
+            input.drain(at..end);
+        }
+        4 => {
+            let chunk = input[at..end].to_vec();
+            let to = rng.range(0..=len);
+            input.splice(to..to, chunk);
+        }
+        5 => input.truncate(at),
+        _ => {
+            let other = rng.choose(pool).unwrap();
+            let from = rng.range(0..=other.len());
+            input.truncate(at);
+            input.extend_from_slice(&other[from..]);
+        }
+    }
+    input.truncate(MAX_LEN);
+}
+
+/// Every file in `corpus`, by name so they're always replayed in the same
+/// order. A missing directory is an empty corpus.
+pub fn read_corpus(corpus: &Path) -> std::io::Result<Vec<(PathBuf, Vec<u8>)>> {
+    let mut paths: Vec<PathBuf> = match fs::read_dir(corpus) {
+        Ok(entries) => entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| fs::read(&path).map(|bytes| (path, bytes)))
+        .collect()
+}
+
+/// Save a failing input to `corpus`, named after its contents
+fn save(corpus: &Path, input: &[u8]) -> std::io::Result<PathBuf> {
+    // FNV-1a, only needs to tell inputs apart
+    let hash = input.iter().fold(0xcbf2_9ce4_8422_2325u64, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100_0000_01b3)
+    });
+    fs::create_dir_all(corpus)?;
+    let path = corpus.join(format!("crash-{hash:016x}"));
+    fs::write(&path, input)?;
+    Ok(path)
+}
+
+/// Run `target` on the inputs in `corpus` and `seeds`, then on mutations of
+/// them as `mode` says, returning how many inputs it got through or the first
+/// one it panicked or hung on
+pub fn run<F>(corpus: &Path, seeds: &[Vec<u8>], mode: Mode, target: F) -> Result<usize, Crash>
+where
+    F: Fn(&[u8]) + Send + 'static,
+{
+    install_panic_hook();
+    let mut pool: Vec<Vec<u8>> = read_corpus(corpus)
+        .unwrap_or_else(|e| panic!("can't read fuzz corpus {}: {e}", corpus.display()))
+        .into_iter()
+        .map(|(_, bytes)| bytes)
+        .collect();
+    pool.extend(seeds.iter().cloned());
+    if pool.is_empty() {
+        pool.push(Vec::new());
+    }
+
+    let (inputs, seed, save_crashes) = match mode {
+        Mode::Replay => (0, 0, false),
+        Mode::Mutate { inputs, seed, save } => (inputs, seed, save),
+    };
+    let mut rng = Rng::new(seed);
+    let mutations = (0..inputs).map(|_| {
+        let mut input = rng.choose(&pool).unwrap().clone();
+        for _ in 0..rng.range(1..=4) {
+            mutate(&mut input, &pool, &mut rng);
+        }
+        input
+    });
+    let mut worker = Worker::start(target);
+    let replayed = pool.clone().into_iter().map(|input| (input, false));
+    let mut count = 0;
+    for (mut input, mutated) in replayed.chain(mutations.map(|input| (input, true))) {
+        let mut problem = match worker.attempt(&input) {
+            Ok(()) => {
+                count += 1;
+                continue;
+            }
+            Err(problem) => problem,
+        };
+        if let Problem::Panic(_) = problem {
+            worker.minimize(&mut input, &mut problem);
+        }
+        let saved = save_crashes.then(|| {
+            save(corpus, &input)
+                .unwrap_or_else(|e| panic!("can't save a crash to {}: {e}", corpus.display()))
+        });
+        return Err(Crash {
+            input,
+            problem,
+            seed: mutated.then_some(seed),
+            saved,
+        });
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Panics on any input containing `!`, and hangs on `?`
+    fn fussy(input: &[u8]) {
+        if input.contains(&b'!') {
+            panic!("found a !");
+        }
+        while input.contains(&b'?') {
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    fn corpus(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("aoc-fuzz-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn finds_panics_and_hangs() {
+        let quick = Mode::Mutate {
+            inputs: 1000,
+            seed: 1,
+            save: false,
+        };
+        let seeds = [b"all fine here".to_vec()];
+        assert_eq!(run(&corpus("none"), &seeds, quick, |_| ()).unwrap(), 1001);
+        assert_eq!(
+            run(&corpus("none"), &seeds, Mode::Replay, fussy).unwrap(),
+            1
+        );
+
+        // Found by mutation, and shrunk down to just the !
+        let seeds = [b"fine!".to_vec(), b"also fine".to_vec()];
+        let crash = run(&corpus("none"), &seeds[1..], quick, |input| {
+            if input.windows(2).any(|pair| pair == b"e!" || pair == b"ee") {
+                panic!("too excited");
+            }
+        })
+        .unwrap_err();
+        assert_eq!(crash.seed, Some(1));
+        assert!(crash.input == b"ee" || crash.input == b"e!", "{crash}");
+        assert!(
+            matches!(&crash.problem, Problem::Panic(message) if message.contains("too excited"))
+        );
+
+        let crash = run(&corpus("none"), &seeds, Mode::Replay, fussy).unwrap_err();
+        assert_eq!(crash.input, b"!");
+        assert_eq!(crash.seed, None);
+
+        let crash = run(&corpus("none"), &[b"what?".to_vec()], Mode::Replay, fussy).unwrap_err();
+        assert_eq!(crash.problem, Problem::Hang(TIMEOUT));
+        assert_eq!(crash.input, b"what?");
+    }
+
+    #[test]
+    fn saves_and_replays_crashes() {
+        let corpus = corpus("saved");
+        let _ = fs::remove_dir_all(&corpus);
+        let save = Mode::Mutate {
+            inputs: 0,
+            seed: 0,
+            save: true,
+        };
+        let crash = run(&corpus, &[b"oh!".to_vec()], save, fussy).unwrap_err();
+        let saved = crash.saved.unwrap();
+        assert_eq!(fs::read(&saved).unwrap(), b"!");
+
+        let crash = run(&corpus, &[], Mode::Replay, fussy).unwrap_err();
+        assert_eq!(crash.input, b"!");
+        fs::remove_dir_all(&corpus).unwrap();
+    }
+}
@@ -0,0 +1,332 @@
+//! Rectangular 2D grid stored in a single `Vec`.
+//!
+//! Positions are `(row, col)` pairs with `(0, 0)` in the top left.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+/// A `(row, col)` position in a [Grid]
+pub type Position = (usize, usize);
+
+const OFFSETS_4: [(isize, isize); 4] = [(-1, 0), (0, -1), (1, 0), (0, 1)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from cells listed row by row.
+    ///
+    /// Panics if the cells don't fill a whole number of rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells can't be split into rows of {width}",
+            cells.len()
+        );
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Build a grid from the lines of `text`, converting each char with `f`.
+    ///
+    /// Returns `None` if `f` does, or if the lines aren't all the same length.
+    pub fn from_lines(text: &str, mut f: impl FnMut(char) -> Option<T>) -> Option<Self> {
+        let mut width: Option<usize> = None;
+        let mut cells: Vec<T> = Vec::new();
+        for line in text.lines() {
+            let before = cells.len();
+            for c in line.chars() {
+                cells.push(f(c)?);
+            }
+            let line_width = cells.len() - before;
+            if *width.get_or_insert(line_width) != line_width {
+                return None;
+            }
+        }
+        match width {
+            Some(width) if width > 0 => Some(Grid::from_vec(width, cells)),
+            _ => None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of cells in the grid
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, col): Position) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        match self.contains(position) {
+            true => Some(&self.cells[position.0 * self.width + position.1]),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        match self.contains(position) {
+            true => Some(&mut self.cells[position.0 * self.width + position.1]),
+            false => None,
+        }
+    }
+
+    /// Cells in row order
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every position in row order
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| (i / width, i % width))
+    }
+
+    /// Cells along with their position, in row order
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> core::slice::Chunks<'_, T> {
+        self.cells.chunks(self.width)
+    }
+
+    /// Cells of one column, top to bottom
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {col} out of range");
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// Positions above, left, below and right of `position` that are in the
+    /// grid
+    pub fn neighbors4(&self, position: Position) -> Neighbors {
+        Neighbors::new(self, position, &OFFSETS_4)
+    }
+
+    /// Positions of all 8 surrounding cells, including diagonals, that are in
+    /// the grid
+    pub fn neighbors8(&self, position: Position) -> Neighbors {
+        Neighbors::new(self, position, &OFFSETS_8)
+    }
+
+    /// Convert every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Repeat the grid `across` times to the right and `down` times below.
+    ///
+    /// `f` gets each cell and the `(row, col)` of the tile it's copied into.
+    /// Panics unless there's at least one tile each way.
+    pub fn tile(&self, across: usize, down: usize, mut f: impl FnMut(&T, Position) -> T) -> Self {
+        assert!(across > 0 && down > 0, "a grid can't be tiled 0 times");
+        let width = self.width * across;
+        let height = self.height * down;
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+        for row in 0..height {
+            for col in 0..width {
+                let tile = (row / self.height, col / self.width);
+                cells.push(f(&self[(row % self.height, col % self.width)], tile));
+            }
+        }
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Grid with every cell set to `value`.
+    ///
+    /// Panics if `width` is 0, since a grid's rows couldn't be told apart.
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        assert!(width > 0, "a grid can't have rows of 0 cells");
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl Grid<u32> {
+    /// Parse lines of single digits like `"0123\n4567"`
+    pub fn from_digits(text: &str) -> Option<Self> {
+        Grid::from_lines(text, |c| c.to_digit(10))
+    }
+}
+
+impl Grid<char> {
+    pub fn from_chars(text: &str) -> Option<Self> {
+        Grid::from_lines(text, Some)
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Position) -> &T {
+        assert!(col < self.width, "column {col} out of range");
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Position) -> &mut T {
+        assert!(col < self.width, "column {col} out of range");
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+/// Draws one row per line with no separator between cells
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (index, row) in self.rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Iterator over the in-bounds positions next to a cell
+pub struct Neighbors {
+    center: Position,
+    width: usize,
+    height: usize,
+    offsets: &'static [(isize, isize)],
+}
+
+impl Neighbors {
+    fn new<T>(grid: &Grid<T>, center: Position, offsets: &'static [(isize, isize)]) -> Self {
+        Neighbors {
+            center,
+            width: grid.width,
+            height: grid.height,
+            offsets,
+        }
+    }
+}
+
+impl Iterator for Neighbors {
+    type Item = Position;
+
+    fn next(&mut self) -> Option<Position> {
+        while let Some((&(d_row, d_col), rest)) = self.offsets.split_first() {
+            self.offsets = rest;
+            let row = self.center.0.checked_add_signed(d_row);
+            let col = self.center.1.checked_add_signed(d_col);
+            if let (Some(row), Some(col)) = (row, col) {
+                if row < self.height && col < self.width {
+                    return Some((row, col));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_index() {
+        let grid = Grid::from_digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.row(0), &[1, 2, 3]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<u32>>(), vec![3, 6]);
+        assert_eq!(grid.get((2, 0)), None);
+
+        assert!(Grid::from_digits("12\n345").is_none());
+        assert!(Grid::from_digits("1a").is_none());
+        assert!(Grid::from_digits("").is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "rows of 0 cells")]
+    fn zero_width() {
+        Grid::new(0, 3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "tiled 0 times")]
+    fn zero_tiles() {
+        Grid::new(2, 2, 0).tile(0, 2, |&x, _| x);
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((0, 2)).count(), 3);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn tile_and_display() {
+        let grid = Grid::from_digits("89").unwrap();
+        let tiled = grid.tile(2, 2, |&x, (row, col)| (x + (row + col) as u32 - 1) % 9 + 1);
+        assert_eq!(tiled.to_string(), "8991\n9112");
+    }
+}
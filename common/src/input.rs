@@ -0,0 +1,173 @@
+//! Finding and reading a day's puzzle input.
+//!
+//! Every day accepts the same input argument, which is resolved in this order:
+//!
+//! 1. `-` reads from stdin
+//! 2. a path to an existing file
+//! 3. the name of an input variant in the day's directory, so `test` reads
+//!    `dayN/test_input.txt`, `test0` reads `dayN/test0_input.txt` and `input`
+//!    (the default when no argument is given) reads `dayN/input.txt`
+//!
+//! The day directories are found under `$AOC_INPUT_DIR` when it's set,
+//! otherwise in this workspace.
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable holding the directory that contains the `dayN`
+/// input directories
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Variant used when no input argument is given
+pub const DEFAULT_VARIANT: &str = "input";
+
+/// Where a day's input will be read from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                Ok(text)
+            }
+            InputSource::File(path) => fs::read_to_string(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display()))),
+        }
+    }
+}
+
+impl std::fmt::Display for InputSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Directory holding the `dayN` input directories
+pub fn input_root() -> PathBuf {
+    match env::var_os(INPUT_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("common is always inside the workspace")
+            .to_path_buf(),
+    }
+}
+
+/// Directory holding the inputs for `day`
+pub fn day_dir(day: u8) -> PathBuf {
+    input_root().join(format!("day{day}"))
+}
+
+/// File name of an input variant, `test` becomes `test_input.txt`
+fn variant_file(variant: &str) -> String {
+    match variant {
+        DEFAULT_VARIANT => "input.txt".to_owned(),
+        _ => format!("{variant}_input.txt"),
+    }
+}
+
+/// Names of the input variants available in `dir`, sorted
+fn variants_in(dir: &Path) -> Vec<String> {
+    let mut variants: Vec<String> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|name| match name.as_str() {
+                "input.txt" => Some(DEFAULT_VARIANT.to_owned()),
+                _ => name.strip_suffix("_input.txt").map(|x| x.to_owned()),
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    variants.sort();
+    variants
+}
+
+/// Names of the input variants available for `day`, like `input` or `test0`
+pub fn variants(day: u8) -> Vec<String> {
+    variants_in(&day_dir(day))
+}
+
+fn resolve_in(dir: &Path, arg: Option<&str>) -> io::Result<InputSource> {
+    let arg = arg.unwrap_or(DEFAULT_VARIANT);
+    if arg == "-" {
+        return Ok(InputSource::Stdin);
+    }
+
+    let path = Path::new(arg);
+    if path.is_file() {
+        return Ok(InputSource::File(path.to_path_buf()));
+    }
+
+    let variant = dir.join(variant_file(arg));
+    if variant.is_file() {
+        return Ok(InputSource::File(variant));
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!(
+            "{arg:?} is not a file or an input variant in {} (have {:?})",
+            dir.display(),
+            variants_in(dir)
+        ),
+    ))
+}
+
+/// Work out where the input for `day` comes from, see the module docs
+pub fn resolve(day: u8, arg: Option<&str>) -> io::Result<InputSource> {
+    resolve_in(&day_dir(day), arg)
+}
+
+/// Resolve and read the input for `day`
+pub fn read(day: u8, arg: Option<&str>) -> io::Result<String> {
+    resolve(day, arg)?.read()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolution_order() {
+        let dir = env::temp_dir().join(format!("aoc-input-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("input.txt"), "real").unwrap();
+        fs::write(dir.join("test0_input.txt"), "test0").unwrap();
+
+        assert_eq!(resolve_in(&dir, Some("-")).unwrap(), InputSource::Stdin);
+        assert_eq!(
+            resolve_in(&dir, None).unwrap(),
+            InputSource::File(dir.join("input.txt"))
+        );
+        assert_eq!(
+            resolve_in(&dir, Some("test0")).unwrap(),
+            InputSource::File(dir.join("test0_input.txt"))
+        );
+
+        // paths win over variants
+        let path = dir.join("test0_input.txt");
+        assert_eq!(
+            resolve_in(&dir, path.to_str()).unwrap(),
+            InputSource::File(path)
+        );
+
+        let missing = resolve_in(&dir, Some("test")).unwrap_err();
+        assert_eq!(missing.kind(), io::ErrorKind::NotFound);
+        assert_eq!(variants_in(&dir), vec!["input", "test0"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
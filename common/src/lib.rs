@@ -1,11 +1,49 @@
-use std::{
-    fs::File,
-    io::{self, BufRead, BufReader},
-    path::Path,
+//! Shared pieces of every day's solution.
+//!
+//! Parsing and solving only need `core` and `alloc`, so they work on targets
+//! without an operating system. Reading inputs and printing answers need the
+//! `std` feature, which is on by default.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod animation;
+pub mod check;
+#[cfg(feature = "std")]
+mod cli;
+mod error;
+#[cfg(feature = "std")]
+pub mod fuzz;
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod input;
+mod json;
+pub mod log;
+pub mod outline;
+pub mod parse;
+pub mod random;
+pub mod render;
+pub mod search;
+mod solution;
+
+#[cfg(feature = "std")]
+pub use cli::{
+    print_answer, print_answers, print_part, print_record, run_main, run_main_with_args,
+    write_picture, Format, Options,
 };
+pub use error::AocError;
+pub use grid::Grid;
+pub use json::Json;
+pub use solution::{answer_record, register, Answer, DynSolution, Part, Solution};
+
+#[cfg(feature = "std")]
+pub fn read_input(filename: impl AsRef<std::path::Path>) -> std::io::Result<Vec<String>> {
+    use std::io::BufRead;
 
-pub fn read_input(filename: impl AsRef<Path>) -> io::Result<Vec<String>> {
-    BufReader::new(File::open(filename)?).lines().collect()
+    std::io::BufReader::new(std::fs::File::open(filename)?)
+        .lines()
+        .collect()
 }
 
 #[cfg(test)]
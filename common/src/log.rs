@@ -0,0 +1,321 @@
+//! Levelled debug output that can be turned on without recompiling.
+//!
+//! Solvers log through the [error!](crate::error!), [warn!](crate::warn!),
+//! [info!](crate::info!), [debug!](crate::debug!) and [trace!](crate::trace!)
+//! macros, each tagged with a target, the crate it's in, so one day can be
+//! traced on its own. Nothing is printed until a [Logger] is installed with
+//! [set_logger]. With the `std` feature, [StderrLogger] prints to stderr and
+//! the binaries install it for `-v` (debug), `-vv` (trace) or a `--log`
+//! filter like `day16=trace,info`.
+//!
+//! Checking whether a message is wanted costs one atomic load, so logging in
+//! hot loops is cheap while it's off. The message is only formatted when it
+//! will be printed.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+/// How important a message is, from most to least
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    /// Parse a level name like `debug`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Level::ALL
+            .into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(name.trim()))
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    /// The level shown for `-v` repeated `count` times, starting from info
+    pub fn from_verbosity(count: u8) -> Self {
+        match count {
+            0 => Level::Info,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A message on its way to the [Logger]
+#[derive(Clone, Copy, Debug)]
+pub struct Record<'a> {
+    pub level: Level,
+    /// The crate that logged it, like `day16`
+    pub target: &'a str,
+    pub args: fmt::Arguments<'a>,
+}
+
+/// Where log messages go
+pub trait Logger: Sync {
+    /// Whether messages at `level` from `target` are wanted
+    fn enabled(&self, level: Level, target: &str) -> bool;
+    fn log(&self, record: &Record);
+}
+
+/// Most verbose level any target wants, 0 when logging is off
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(0);
+
+/// Set once `LOGGER` is safe to read
+static STATE: AtomicU8 = AtomicU8::new(UNSET);
+const UNSET: u8 = 0;
+const SETTING: u8 = 1;
+const SET: u8 = 2;
+
+static mut LOGGER: &dyn Logger = &NoLogger;
+
+struct NoLogger;
+
+impl Logger for NoLogger {
+    fn enabled(&self, _level: Level, _target: &str) -> bool {
+        false
+    }
+
+    fn log(&self, _record: &Record) {}
+}
+
+/// Send log messages up to `max_level` to `logger`. Only the first call
+/// installs a logger, later ones return false.
+pub fn set_logger(logger: &'static dyn Logger, max_level: Level) -> bool {
+    if STATE
+        .compare_exchange(UNSET, SETTING, Ordering::Acquire, Ordering::Relaxed)
+        .is_err()
+    {
+        return false;
+    }
+    // SAFETY: the compare exchange above lets only one caller get here, and
+    // `logger()` doesn't read LOGGER until STATE is SET
+    unsafe {
+        LOGGER = logger;
+    }
+    STATE.store(SET, Ordering::Release);
+    MAX_LEVEL.store(max_level as u8, Ordering::Relaxed);
+    true
+}
+
+fn logger() -> &'static dyn Logger {
+    match STATE.load(Ordering::Acquire) {
+        // SAFETY: LOGGER is never written again once STATE is SET
+        SET => unsafe { LOGGER },
+        _ => &NoLogger,
+    }
+}
+
+/// Whether a message at `level` from `target` would be printed
+#[inline]
+pub fn enabled(level: Level, target: &str) -> bool {
+    level as u8 <= MAX_LEVEL.load(Ordering::Relaxed) && logger().enabled(level, target)
+}
+
+/// Used by the macros, which check [enabled] first
+#[doc(hidden)]
+pub fn log(level: Level, target: &str, args: fmt::Arguments) {
+    logger().log(&Record {
+        level,
+        target,
+        args,
+    });
+}
+
+/// The crate a module path like `day16::decode` belongs to
+#[doc(hidden)]
+pub const fn crate_name(module_path: &'static str) -> &'static str {
+    let bytes = module_path.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b':' {
+            let (name, _) = module_path.split_at(i);
+            return name;
+        }
+        i += 1;
+    }
+    module_path
+}
+
+/// Whether a filter for `filter` covers messages from `target`. `day6`
+/// covers `day6_2` but not `day16`.
+pub fn target_matches(filter: &str, target: &str) -> bool {
+    match target.strip_prefix(filter) {
+        Some(rest) => !rest.starts_with(|x: char| x.is_ascii_alphanumeric()),
+        None => false,
+    }
+}
+
+/// Which level each target logs at, parsed from a list like
+/// `day16=trace,day11=debug,warn` where a bare level applies to every other
+/// target
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Filter {
+    pub default: Level,
+    pub targets: Vec<(String, Level)>,
+}
+
+impl Filter {
+    pub fn new(default: Level) -> Self {
+        Filter {
+            default,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Add the settings in `spec` on top of these
+    pub fn parse(mut self, spec: &str) -> Result<Self, String> {
+        for item in spec.split(',').map(str::trim).filter(|x| !x.is_empty()) {
+            let invalid = || format!("Invalid log level in {item:?}");
+            match item.split_once('=') {
+                Some((target, level)) => {
+                    let level = Level::from_name(level).ok_or_else(invalid)?;
+                    self.targets.push((target.trim().to_owned(), level));
+                }
+                None => self.default = Level::from_name(item).ok_or_else(invalid)?,
+            }
+        }
+        Ok(self)
+    }
+
+    /// The most verbose level a message from `target` can have, the last
+    /// matching target winning
+    pub fn level(&self, target: &str) -> Level {
+        self.targets
+            .iter()
+            .rev()
+            .find(|(filter, _)| target_matches(filter, target))
+            .map_or(self.default, |(_, level)| *level)
+    }
+
+    /// The most verbose level of any target
+    pub fn max_level(&self) -> Level {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Level::max)
+    }
+}
+
+/// Prints `[day16 trace] message` lines to stderr
+#[cfg(feature = "std")]
+pub struct StderrLogger {
+    pub filter: Filter,
+}
+
+#[cfg(feature = "std")]
+impl Logger for StderrLogger {
+    fn enabled(&self, level: Level, target: &str) -> bool {
+        level <= self.filter.level(target)
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.level, record.target) {
+            std::eprintln!("[{} {}] {}", record.target, record.level, record.args);
+        }
+    }
+}
+
+/// Install a [StderrLogger] for `filter`, unless a logger is already set
+#[cfg(feature = "std")]
+pub fn init(filter: Filter) {
+    let max_level = filter.max_level();
+    // Loggers live for the rest of the program
+    let logger: &'static StderrLogger =
+        std::boxed::Box::leak(std::boxed::Box::new(StderrLogger { filter }));
+    set_logger(logger, max_level);
+}
+
+/// Log a message at a [Level], see the [module docs](mod@crate::log)
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {{
+        let level = $level;
+        let target = const { $crate::log::crate_name(::core::module_path!()) };
+        if $crate::log::enabled(level, target) {
+            $crate::log::log(level, target, ::core::format_args!($($arg)+));
+        }
+    }};
+}
+
+/// Log at [Level::Error]
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Error, $($arg)+) };
+}
+
+/// Log at [Level::Warn]
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Warn, $($arg)+) };
+}
+
+/// Log at [Level::Info]
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Info, $($arg)+) };
+}
+
+/// Log at [Level::Debug]
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Debug, $($arg)+) };
+}
+
+/// Log at [Level::Trace]
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => { $crate::log!($crate::log::Level::Trace, $($arg)+) };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filters() {
+        let filter = Filter::new(Level::Info)
+            .parse("day16=trace, day6=debug,warn")
+            .unwrap();
+        assert_eq!(filter.default, Level::Warn);
+        assert_eq!(filter.level("day16"), Level::Trace);
+        assert_eq!(filter.level("day6_2"), Level::Debug);
+        assert_eq!(filter.level("day1"), Level::Warn);
+        assert_eq!(filter.max_level(), Level::Trace);
+        assert!(Filter::new(Level::Info).parse("day16=loud").is_err());
+
+        assert!(target_matches("day1", "day1_2"));
+        assert!(!target_matches("day1", "day16"));
+        assert_eq!(crate_name("day16::bits::reader"), "day16");
+        assert_eq!(crate_name("day16"), "day16");
+    }
+}
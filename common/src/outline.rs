@@ -0,0 +1,82 @@
+//! Trees of labelled nodes, for answers that are better browsed than drawn
+//! like day 16's packets or day 12's paths.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A label with any number of nested children
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Outline {
+    pub label: String,
+    pub children: Vec<Outline>,
+}
+
+impl Outline {
+    /// A node without children
+    pub fn leaf(label: impl Into<String>) -> Self {
+        Outline {
+            label: label.into(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn new(label: impl Into<String>, children: Vec<Outline>) -> Self {
+        Outline {
+            label: label.into(),
+            children,
+        }
+    }
+
+    /// Follow child indices down from this node
+    pub fn get(&self, path: &[usize]) -> Option<&Outline> {
+        path.iter()
+            .try_fold(self, |node, &index| node.children.get(index))
+    }
+
+    /// Number of nodes, counting this one
+    pub fn len(&self) -> usize {
+        1 + self.children.iter().map(Outline::len).sum::<usize>()
+    }
+
+    /// Always false, there's at least this node
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+}
+
+/// One node per line, children indented by two spaces
+impl fmt::Display for Outline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut stack: Vec<(usize, &Outline)> = Vec::from([(0, self)]);
+        while let Some((depth, node)) = stack.pop() {
+            writeln!(f, "{:indent$}{}", "", node.label, indent = depth * 2)?;
+            stack.extend(node.children.iter().rev().map(|x| (depth + 1, x)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_and_paths() {
+        let outline = Outline::new(
+            "sum",
+            Vec::from([
+                Outline::leaf("1"),
+                Outline::new(
+                    "product",
+                    Vec::from([Outline::leaf("2"), Outline::leaf("3")]),
+                ),
+            ]),
+        );
+        assert_eq!(outline.to_string(), "sum\n  1\n  product\n    2\n    3\n");
+        assert_eq!(outline.len(), 5);
+        assert_eq!(outline.get(&[1, 0]).map(|x| x.label.as_str()), Some("2"));
+        assert_eq!(outline.get(&[]), Some(&outline));
+        assert_eq!(outline.get(&[2]), None);
+    }
+}
@@ -0,0 +1,384 @@
+//! Parsers for the pieces that puzzle inputs are built from.
+//!
+//! Everything works on [Span]s, slices of the input that remember the line
+//! and column they start at, so a failure can point at the spot in the input
+//! that couldn't be parsed instead of panicking somewhere inside a day.
+//!
+//! ```
+//! use common::parse::{csv_numbers, sections, separated_pair};
+//!
+//! let input = "7,4,9\n\nNN -> C\nNC -> B\n";
+//! let sections = sections(input);
+//! assert_eq!(csv_numbers::<u32>(sections[0]).unwrap(), vec![7, 4, 9]);
+//!
+//! let rule = sections[1].lines().nth(1).unwrap();
+//! let (pair, insert) = separated_pair(rule, "->").unwrap();
+//! assert_eq!((pair.as_str(), insert.as_str()), ("NC", "B"));
+//!
+//! let error = csv_numbers::<u32>("1,x,3").unwrap_err();
+//! assert_eq!((error.line, error.column), (1, 3));
+//! ```
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::RangeInclusive;
+use core::str::FromStr;
+
+use crate::Grid;
+
+/// Where and why some input couldn't be parsed
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the input, starting from 1
+    pub line: usize,
+    /// Column in chars, starting from 1
+    pub column: usize,
+    /// The text that failed to parse, cut off at the end of its line
+    pub text: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}, found {:?}",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// A slice of the input along with where it starts
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Span<'a> {
+    text: &'a str,
+    line: usize,
+    column: usize,
+}
+
+/// Finds the spans of parts of a span in order, counting lines and columns
+/// on from the previous part rather than from the start of the span each
+/// time, which gets slow on big inputs
+struct Cursor<'a> {
+    /// The span from the end of the previous part
+    rest: Span<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    /// Span of `part`, which must be a slice of the text after the previous
+    /// part
+    fn slice(&mut self, part: &'a str) -> Span<'a> {
+        let span = self.rest.slice(part);
+        let offset = part.as_ptr() as usize - self.rest.text.as_ptr() as usize;
+        self.rest = Span {
+            text: &self.rest.text[offset..],
+            ..span
+        };
+        span
+    }
+}
+
+impl<'a> From<&'a str> for Span<'a> {
+    fn from(text: &'a str) -> Self {
+        Span::new(text)
+    }
+}
+
+impl<'a> Span<'a> {
+    /// The whole of `text`, starting at line 1 column 1
+    pub fn new(text: &'a str) -> Self {
+        Span {
+            text,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    /// Span of `part`, which must be a slice of this span's text
+    fn slice(&self, part: &'a str) -> Span<'a> {
+        let offset = part.as_ptr() as usize - self.text.as_ptr() as usize;
+        debug_assert!(offset + part.len() <= self.text.len());
+        let before = &self.text[..offset];
+        match before.rfind('\n') {
+            Some(newline) => Span {
+                text: part,
+                line: self.line + before.matches('\n').count(),
+                column: before[newline + 1..].chars().count() + 1,
+            },
+            None => Span {
+                text: part,
+                line: self.line,
+                column: self.column + before.chars().count(),
+            },
+        }
+    }
+
+    /// The bytes at `range` of this span, like [str::get], or `None` when the
+    /// range runs off the end or splits a char
+    pub fn get(&self, range: impl core::slice::SliceIndex<str, Output = str>) -> Option<Span<'a>> {
+        self.text.get(range).map(|part| self.slice(part))
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.slice(self.text.trim())
+    }
+
+    /// Lines without their `\n` or `\r\n`, like [str::lines]
+    pub fn lines(self) -> impl Iterator<Item = Span<'a>> + 'a {
+        // Counted as we go, working each line out from the start of the span
+        // gets slow on big inputs
+        let mut next = (self.line, self.column);
+        self.text.split_inclusive('\n').map(move |line| {
+            let text = line.strip_suffix('\n').unwrap_or(line);
+            let text = text.strip_suffix('\r').unwrap_or(text);
+            let (line, column) = next;
+            next = (line + 1, 1);
+            Span { text, line, column }
+        })
+    }
+
+    pub fn split<'s>(self, separator: &'s str) -> impl Iterator<Item = Span<'a>> + 's
+    where
+        'a: 's,
+    {
+        let mut cursor = Cursor { rest: self };
+        self.text
+            .split(separator)
+            .map(move |part| cursor.slice(part))
+    }
+
+    pub fn split_whitespace(self) -> impl Iterator<Item = Span<'a>> + 'a {
+        let mut cursor = Cursor { rest: self };
+        self.text
+            .split_whitespace()
+            .map(move |part| cursor.slice(part))
+    }
+
+    pub fn split_once(&self, separator: &str) -> Option<(Span<'a>, Span<'a>)> {
+        let (left, right) = self.text.split_once(separator)?;
+        let mut cursor = Cursor { rest: *self };
+        Some((cursor.slice(left), cursor.slice(right)))
+    }
+
+    pub fn strip_prefix(&self, prefix: &str) -> Option<Span<'a>> {
+        self.text.strip_prefix(prefix).map(|rest| self.slice(rest))
+    }
+
+    /// Each char along with its span
+    pub fn chars(self) -> impl Iterator<Item = (char, Span<'a>)> + 'a {
+        let mut next = (self.line, self.column);
+        self.text.char_indices().map(move |(i, c)| {
+            let (line, column) = next;
+            next = match c {
+                '\n' => (line + 1, 1),
+                _ => (line, column + 1),
+            };
+            let text = &self.text[i..i + c.len_utf8()];
+            (c, Span { text, line, column })
+        })
+    }
+
+    /// An error pointing at the start of this span
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            text: self.text.lines().next().unwrap_or("").to_owned(),
+            message: message.into(),
+        }
+    }
+
+    /// Parse the whole span with [FromStr]
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.text.parse::<T>().map_err(|e| {
+            let name = core::any::type_name::<T>();
+            let name = name.rsplit("::").next().unwrap_or(name);
+            self.error(format!("expected {name} ({e})"))
+        })
+    }
+}
+
+impl fmt::Display for Span<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Numbers separated by commas like `3,4,3,1,2`
+pub fn csv_numbers<'a, T>(input: impl Into<Span<'a>>) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let input = input.into().trim();
+    input.split(",").map(|x| x.trim().parse::<T>()).collect()
+}
+
+/// Groups of lines separated by blank lines, without the blank lines
+pub fn sections<'a>(input: impl Into<Span<'a>>) -> Vec<Span<'a>> {
+    let input = input.into();
+    let text = input.as_str();
+    let mut sections: Vec<Span<'a>> = Vec::new();
+    let mut cursor = Cursor { rest: input };
+
+    // byte range of the section being built
+    let mut start: Option<usize> = None;
+    let mut end: usize = 0;
+
+    let mut offset: usize = 0;
+    for line in text.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            if let Some(start) = start.take() {
+                sections.push(cursor.slice(&text[start..end]));
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        sections.push(cursor.slice(&text[start..end]));
+    }
+    sections
+}
+
+/// The trimmed text either side of `separator`, like `0,9 -> 5,9`
+pub fn separated_pair<'a>(
+    input: impl Into<Span<'a>>,
+    separator: &str,
+) -> Result<(Span<'a>, Span<'a>), ParseError> {
+    let input = input.into();
+    match input.split_once(separator) {
+        Some((left, right)) => Ok((left.trim(), right.trim())),
+        None => Err(input.error(format!("expected {separator:?}"))),
+    }
+}
+
+/// Lines of single digits, all the same length
+pub fn digit_grid<'a>(input: impl Into<Span<'a>>) -> Result<Grid<u32>, ParseError> {
+    let input = input.into();
+    let mut width: Option<usize> = None;
+    let mut cells: Vec<u32> = Vec::new();
+    for line in input.lines() {
+        let before = cells.len();
+        for (c, span) in line.chars() {
+            match c.to_digit(10) {
+                Some(digit) => cells.push(digit),
+                None => return Err(span.error("expected a digit")),
+            }
+        }
+        let line_width = cells.len() - before;
+        let width = *width.get_or_insert(line_width);
+        if line_width != width {
+            return Err(line.error(format!("expected a row of {width} digits")));
+        }
+    }
+    match width {
+        Some(width) if width > 0 => Ok(Grid::from_vec(width, cells)),
+        _ => Err(input.error("expected a grid of digits")),
+    }
+}
+
+/// An inclusive range written as `name=start..end`, like `x=20..30`
+pub fn range<'a, T>(input: impl Into<Span<'a>>, name: &str) -> Result<RangeInclusive<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let input = input.into().trim();
+    let bounds = input
+        .strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix("="))
+        .ok_or_else(|| input.error(format!("expected \"{name}=\"")))?;
+    let (start, end) = separated_pair(bounds, "..")?;
+    Ok(start.parse()?..=end.parse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans_track_position() {
+        let input = Span::new("ab\n  cd ef\n");
+        let line = input.lines().nth(1).unwrap();
+        assert_eq!((line.line(), line.column()), (2, 1));
+        let word = line.split_whitespace().nth(1).unwrap();
+        assert_eq!((word.as_str(), word.line(), word.column()), ("ef", 2, 6));
+
+        let part = line.get(3..).unwrap();
+        assert_eq!((part.as_str(), part.column()), ("d ef", 4));
+        assert_eq!(line.get(5..20), None);
+
+        let parts: Vec<(&str, usize, usize)> = Span::new("1,2\n3,\n4")
+            .split(",")
+            .map(|x| (x.as_str(), x.line(), x.column()))
+            .collect();
+        assert_eq!(parts, vec![("1", 1, 1), ("2\n3", 1, 3), ("\n4", 2, 3)]);
+        let (left, right) = Span::new("a\nb -> c").split_once("->").unwrap();
+        assert_eq!((left.line(), right.line(), right.column()), (1, 2, 5));
+    }
+
+    #[test]
+    fn sections_skip_blank_lines() {
+        let input = "1,2\n\n\nab\ncd\n\nef";
+        let found: Vec<(&str, usize)> = sections(input)
+            .iter()
+            .map(|x| (x.as_str(), x.line()))
+            .collect();
+        assert_eq!(found, vec![("1,2", 1), ("ab\ncd", 4), ("ef", 7)]);
+    }
+
+    #[test]
+    fn errors_point_at_the_problem() {
+        let error = csv_numbers::<u32>(" 3,4,-3").unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (1, 6, "-3")
+        );
+
+        let error = separated_pair("0,9 => 5,9", "->").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 1));
+
+        let error = digit_grid("123\n456\n4x6").unwrap_err();
+        assert_eq!((error.line, error.column), (3, 2));
+        let error = digit_grid("123\n45\n").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+
+        assert!(range::<i32>("y=-10..-5", "x").is_err());
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(range::<i32>(" y=-10..-5", "y").unwrap(), -10..=-5);
+        let grid = digit_grid("12\n34\n").unwrap();
+        assert_eq!(grid[(1, 0)], 3);
+    }
+}
@@ -0,0 +1,134 @@
+//! Reproducible pseudo-random numbers, for generating puzzle inputs.
+//!
+//! This is SplitMix64: fast, small and plenty random for making up inputs,
+//! though nothing to rely on for secrets. The same seed always gives the same
+//! numbers on every platform.
+
+use core::ops::RangeInclusive;
+
+/// A pseudo-random number generator seeded with a `u64`
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+/// Integers [Rng::range] can pick from
+pub trait Sample: Copy {
+    fn to_i128(self) -> i128;
+    fn from_i128(value: i128) -> Self;
+}
+
+macro_rules! sample_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Sample for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+sample_integer!(u8, u32, u64, usize, i32, i64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`, without the bias of a plain `%`.
+    ///
+    /// Panics if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "can't pick a number below 0");
+        // Drop the top few values that would make the low numbers likelier
+        let limit = u64::MAX - u64::MAX % bound;
+        loop {
+            let x = self.next_u64();
+            if x < limit {
+                return x % bound;
+            }
+        }
+    }
+
+    /// A number in `range`, including both ends.
+    ///
+    /// Panics if the range is empty.
+    pub fn range<T: Sample>(&mut self, range: RangeInclusive<T>) -> T {
+        let (start, end) = (range.start().to_i128(), range.end().to_i128());
+        assert!(start <= end, "can't pick from an empty range");
+        let span = (end - start + 1) as u128;
+        let offset = match u64::try_from(span) {
+            Ok(span) => self.below(span) as i128,
+            // Only the full range of a 64 bit type is this wide
+            Err(_) => self.next_u64() as i128,
+        };
+        T::from_i128(start + offset)
+    }
+
+    /// True with a probability of `numerator / denominator`
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    /// A random item of `items`, `None` if there are none
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        match items.len() {
+            0 => None,
+            len => items.get(self.below(len as u64) as usize),
+        }
+    }
+
+    /// Put `items` in a random order
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeatable_and_in_range() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..20).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        // The first SplitMix64 output for seed 0
+        assert_eq!(numbers(0)[0], 0xe220_a839_7b1d_cdaf);
+
+        let mut rng = Rng::new(1);
+        for _ in 0..1000 {
+            assert!((-3..=4).contains(&rng.range(-3..=4)));
+            assert!(rng.below(10) < 10);
+        }
+        assert_eq!(rng.range(5u8..=5), 5);
+        let _ = rng.range(i64::MIN..=i64::MAX);
+
+        let mut items: Vec<u32> = (0..50).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..50).collect::<Vec<u32>>());
+        items.sort();
+        assert_eq!(items, (0..50).collect::<Vec<u32>>());
+        assert_eq!(rng.choose::<u32>(&[]), None);
+    }
+}
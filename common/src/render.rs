@@ -0,0 +1,429 @@
+//! Drawing a day's state as a picture.
+//!
+//! A [Picture] is a grid of cells, each a glyph for text output and a color
+//! for everything else. The [Render] backends turn one into the bytes of a
+//! file: [Ascii] text (optionally with ANSI colors for a terminal), [Ppm] and
+//! [Png] images, and [Svg].
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::grid::Grid;
+
+/// An RGB color
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Color(pub u8, pub u8, pub u8);
+
+impl Color {
+    pub const BLACK: Color = Color(0, 0, 0);
+    pub const WHITE: Color = Color(255, 255, 255);
+    pub const GRAY: Color = Color(96, 96, 96);
+    pub const RED: Color = Color(220, 50, 47);
+    pub const YELLOW: Color = Color(250, 200, 40);
+    pub const BLUE: Color = Color(38, 110, 210);
+    /// Default for blank cells
+    pub const BACKGROUND: Color = Color(16, 16, 24);
+
+    /// Part way from `self` to `other`, `amount` from 0 to 1
+    pub fn mix(self, other: Color, amount: f32) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount + 0.5) as u8;
+        Color(
+            channel(self.0, other.0),
+            channel(self.1, other.1),
+            channel(self.2, other.2),
+        )
+    }
+
+    /// A color for each index that's easy to tell from its neighbors, for
+    /// regions like day 9's basins
+    pub fn palette(index: usize) -> Color {
+        const PALETTE: [Color; 8] = [
+            Color(230, 97, 1),
+            Color(94, 60, 153),
+            Color(27, 158, 119),
+            Color(231, 41, 138),
+            Color(102, 166, 30),
+            Color(230, 171, 2),
+            Color(31, 120, 180),
+            Color(166, 118, 29),
+        ];
+        PALETTE[index % PALETTE.len()]
+    }
+
+    /// `#rrggbb`
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// One square of a [Picture]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    /// Shown by text output
+    pub glyph: char,
+    /// Fills the square in images, and colors the glyph in a terminal
+    pub color: Color,
+}
+
+impl Cell {
+    pub const fn new(glyph: char, color: Color) -> Self {
+        Cell { glyph, color }
+    }
+}
+
+/// A grid of cells to render, row 0 at the top
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Picture {
+    pub cells: Grid<Cell>,
+    /// Color of the cells that are left blank, which images don't need to
+    /// draw one by one
+    pub background: Color,
+}
+
+impl Picture {
+    /// Picture with every cell set to `blank`
+    pub fn new(width: usize, height: usize, blank: Cell) -> Self {
+        Picture {
+            cells: Grid::new(width, height, blank),
+            background: blank.color,
+        }
+    }
+
+    /// Draw each cell of `grid` with `f`
+    pub fn from_grid<T>(grid: &Grid<T>, f: impl FnMut(&T) -> Cell) -> Self {
+        Picture {
+            cells: grid.map(f),
+            background: Color::BACKGROUND,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Pixels per cell so that images come out with the longer side around
+    /// 800 pixels
+    pub fn cell_size(&self) -> usize {
+        (800 / self.width().max(self.height()).max(1)).clamp(1, 16)
+    }
+
+    /// Each cell as `cell_size` squared pixels, as RGB rows top to bottom
+    fn pixels(&self, cell_size: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.cells.len() * cell_size * cell_size * 3);
+        for row in self.cells.rows() {
+            for _ in 0..cell_size {
+                for cell in row {
+                    let Color(r, g, b) = cell.color;
+                    for _ in 0..cell_size {
+                        pixels.extend([r, g, b]);
+                    }
+                }
+            }
+        }
+        pixels
+    }
+}
+
+/// Bar chart with a bar for each value, 2 cells wide with a gap between.
+///
+/// Bars are on a log scale, one row for each bit the value needs, so counts
+/// that grow exponentially still fit. Bars past `height` rows are cut off.
+/// Without any bars, the chart is one blank column.
+pub fn log_bars(bars: &[(u64, Color)], height: usize) -> Picture {
+    let blank = Cell::new(' ', Color::BACKGROUND);
+    let width = (bars.len() * 3).saturating_sub(1).max(1);
+    let mut picture = Picture::new(width, height, blank);
+    for (index, &(value, color)) in bars.iter().enumerate() {
+        let rows = ((u64::BITS - value.leading_zeros()) as usize).min(height);
+        for row in height - rows..height {
+            for col in index * 3..index * 3 + 2 {
+                picture.cells[(row, col)] = Cell::new('#', color);
+            }
+        }
+    }
+    picture
+}
+
+/// A file format pictures can be written in
+pub trait Render {
+    /// The contents of the file
+    fn render(&self, picture: &Picture) -> Vec<u8>;
+}
+
+/// The backend for a file name's extension: `.txt`, `.ppm`, `.png` or `.svg`
+pub fn for_file_name(name: &str) -> Option<Box<dyn Render>> {
+    let (_, extension) = name.rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "txt" => Some(Box::new(Ascii { color: false })),
+        "ppm" => Some(Box::new(Ppm::default())),
+        "png" => Some(Box::new(Png::default())),
+        "svg" => Some(Box::new(Svg::default())),
+        _ => None,
+    }
+}
+
+/// One line of glyphs per row, colored with ANSI escapes when `color` is set
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ascii {
+    pub color: bool,
+}
+
+impl Render for Ascii {
+    fn render(&self, picture: &Picture) -> Vec<u8> {
+        let mut text = String::new();
+        for row in picture.cells.rows() {
+            let mut current: Option<Color> = None;
+            for cell in row {
+                if self.color && current != Some(cell.color) {
+                    let Color(r, g, b) = cell.color;
+                    // 24 bit foreground color
+                    let _ = write!(text, "\x1b[38;2;{r};{g};{b}m");
+                    current = Some(cell.color);
+                }
+                text.push(cell.glyph);
+            }
+            if self.color {
+                text.push_str("\x1b[0m");
+            }
+            text.push('\n');
+        }
+        text.into_bytes()
+    }
+}
+
+/// Binary (P6) portable pixmap
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Ppm {
+    /// Pixels per cell, picked from the picture's size when `None`
+    pub cell_size: Option<usize>,
+}
+
+impl Render for Ppm {
+    fn render(&self, picture: &Picture) -> Vec<u8> {
+        let size = self.cell_size.unwrap_or_else(|| picture.cell_size());
+        let mut file = format!(
+            "P6\n{} {}\n255\n",
+            picture.width() * size,
+            picture.height() * size
+        )
+        .into_bytes();
+        file.extend(picture.pixels(size));
+        file
+    }
+}
+
+/// PNG image, stored without compression so it doesn't need a deflate
+/// implementation
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Png {
+    /// Pixels per cell, picked from the picture's size when `None`
+    pub cell_size: Option<usize>,
+}
+
+impl Render for Png {
+    fn render(&self, picture: &Picture) -> Vec<u8> {
+        let size = self.cell_size.unwrap_or_else(|| picture.cell_size());
+        let (width, height) = (picture.width() * size, picture.height() * size);
+
+        let mut header = Vec::new();
+        header.extend((width as u32).to_be_bytes());
+        header.extend((height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, then the only compression, filter and
+        // interlace methods there are
+        header.extend([8, 2, 0, 0, 0]);
+
+        // Every scanline starts with its filter type, 0 for none
+        let pixels = picture.pixels(size);
+        let mut scanlines = Vec::with_capacity(pixels.len() + height);
+        for row in 0..height {
+            scanlines.push(0);
+            scanlines.extend_from_slice(&pixels[row * width * 3..(row + 1) * width * 3]);
+        }
+
+        let mut file = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+        png_chunk(&mut file, b"IHDR", &header);
+        png_chunk(&mut file, b"IDAT", &zlib_stored(&scanlines));
+        png_chunk(&mut file, b"IEND", &[]);
+        file
+    }
+}
+
+/// Append a PNG chunk: its length, type, data and the CRC of type and data
+fn png_chunk(file: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    file.extend((data.len() as u32).to_be_bytes());
+    let start = file.len();
+    file.extend_from_slice(kind);
+    file.extend_from_slice(data);
+    let crc = crc32(&file[start..]);
+    file.extend(crc.to_be_bytes());
+}
+
+/// CRC-32 as used by PNG (and zip, and ethernet)
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ 0xedb8_8320,
+                _ => crc >> 1,
+            };
+        }
+    }
+    !crc
+}
+
+/// A zlib stream holding `data` in uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+    // Deflate with a 32K window and no preset dictionary, chosen so the
+    // header is a multiple of 31
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        stream.push(blocks.peek().is_none() as u8);
+        let length = block.len() as u16;
+        stream.extend(length.to_le_bytes());
+        stream.extend((!length).to_le_bytes());
+        stream.extend_from_slice(block);
+    }
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    stream.extend(((b << 16) | a).to_be_bytes());
+    stream
+}
+
+/// Scalable vector graphics, one rectangle per run of same colored cells
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Svg {
+    /// Units per cell, picked from the picture's size when `None`
+    pub cell_size: Option<usize>,
+}
+
+impl Render for Svg {
+    fn render(&self, picture: &Picture) -> Vec<u8> {
+        let size = self.cell_size.unwrap_or_else(|| picture.cell_size());
+        let (width, height) = (picture.width() * size, picture.height() * size);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             shape-rendering=\"crispEdges\">\n\
+             <rect width=\"{width}\" height=\"{height}\" fill=\"{}\"/>\n",
+            picture.background.hex()
+        );
+
+        for (row_index, row) in picture.cells.rows().enumerate() {
+            let mut col = 0;
+            while col < row.len() {
+                let color = row[col].color;
+                let run = row[col..].iter().take_while(|x| x.color == color).count();
+                if color != picture.background {
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{size}\" fill=\"{}\"/>",
+                        col * size,
+                        row_index * size,
+                        run * size,
+                        color.hex()
+                    );
+                }
+                col += run;
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg.into_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn checkerboard() -> Picture {
+        let mut picture = Picture::new(2, 2, Cell::new('.', Color::BLACK));
+        picture.cells[(0, 0)] = Cell::new('#', Color::WHITE);
+        picture.cells[(1, 1)] = Cell::new('#', Color::RED);
+        picture
+    }
+
+    #[test]
+    fn ascii() {
+        let plain = Ascii { color: false }.render(&checkerboard());
+        assert_eq!(plain, b"#.\n.#\n");
+
+        let colored = String::from_utf8(Ascii { color: true }.render(&checkerboard())).unwrap();
+        assert!(colored.starts_with("\x1b[38;2;255;255;255m#\x1b[38;2;0;0;0m.\x1b[0m\n"));
+    }
+
+    #[test]
+    fn ppm() {
+        let ppm = Ppm { cell_size: Some(1) }.render(&checkerboard());
+        let mut expected = b"P6\n2 2\n255\n".to_vec();
+        expected.extend([255, 255, 255, 0, 0, 0, 0, 0, 0, 220, 50, 47]);
+        assert_eq!(ppm, expected);
+
+        let scaled = Ppm { cell_size: Some(3) }.render(&checkerboard());
+        assert!(scaled.starts_with(b"P6\n6 6\n255\n"));
+        assert_eq!(scaled.len(), 11 + 6 * 6 * 3);
+    }
+
+    #[test]
+    fn png_checksums() {
+        // Known values from the zlib and PNG specs
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(
+            zlib_stored(b"Wikipedia")[2 + 5 + 9..],
+            0x11e6_0398u32.to_be_bytes()
+        );
+
+        let png = Png { cell_size: Some(1) }.render(&checkerboard());
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x02\0\0\0\x02"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+    }
+
+    #[test]
+    fn svg_merges_runs() {
+        let mut picture = Picture::new(3, 1, Cell::new(' ', Color::BLACK));
+        picture.cells[(0, 1)].color = Color::RED;
+        picture.cells[(0, 2)].color = Color::RED;
+        let svg = String::from_utf8(Svg { cell_size: Some(2) }.render(&picture)).unwrap();
+        assert!(svg.contains(r##"<rect x="2" y="0" width="4" height="2" fill="#dc322f"/>"##));
+        assert_eq!(svg.matches("<rect").count(), 2);
+    }
+
+    #[test]
+    fn bars() {
+        let chart = log_bars(
+            &[(0, Color::RED), (5, Color::RED), (u64::MAX, Color::RED)],
+            4,
+        );
+        let text = Ascii { color: false }.render(&chart);
+        assert_eq!(text, b"      ##\n   ## ##\n   ## ##\n   ## ##\n");
+        let empty = Ascii { color: false }.render(&log_bars(&[], 2));
+        assert_eq!(empty, b" \n \n");
+    }
+
+    #[test]
+    fn backend_from_extension() {
+        assert!(for_file_name("day9.PNG").is_some());
+        assert!(for_file_name("out.svg").is_some());
+        assert!(for_file_name("picture.gif").is_none());
+        assert!(for_file_name("noextension").is_none());
+    }
+}
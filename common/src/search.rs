@@ -0,0 +1,530 @@
+//! Searching graphs breadth first, depth first, with Dijkstra or with A*.
+//!
+//! A [Graph] only lists the successors of a node and the cost of moving to
+//! each. Every search also takes a [Visited] set deciding which nodes have
+//! already been seen: a `BTreeSet` or `HashSet` works for any node, a
+//! `Grid<bool>` for grid positions, and [NeverVisited] for trees or for
+//! enumerating every path.
+//!
+//! Searches call `visit` on each node they reach. Returning [Control::Prune]
+//! stops the search going past that node, and [Control::Stop] ends it with
+//! the path to that node.
+
+use alloc::collections::{BTreeSet, BinaryHeap, VecDeque};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::ops::Add;
+
+use crate::grid::{Grid, Position};
+
+/// Total cost of a path, added up edge by edge
+pub trait Cost: Copy + Ord + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+macro_rules! cost_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+            }
+        )*
+    };
+}
+
+cost_for_integer!(u8, u16, u32, u64, usize, i32, i64);
+
+/// A directed graph, given by the edges leaving each node
+pub trait Graph {
+    type Node: Clone;
+    type Cost: Cost;
+
+    /// Nodes reachable in one step from `node`, with the cost of the step
+    fn successors(&self, node: &Self::Node) -> impl Iterator<Item = (Self::Node, Self::Cost)>;
+}
+
+/// The nodes a search has already seen
+pub trait Visited<N> {
+    /// Mark `node` as seen, returning whether it's new
+    fn insert(&mut self, node: &N) -> bool;
+    fn contains(&self, node: &N) -> bool;
+}
+
+impl<N: Ord + Clone> Visited<N> for BTreeSet<N> {
+    fn insert(&mut self, node: &N) -> bool {
+        BTreeSet::insert(self, node.clone())
+    }
+
+    fn contains(&self, node: &N) -> bool {
+        BTreeSet::contains(self, node)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<N: Eq + core::hash::Hash + Clone, S: core::hash::BuildHasher> Visited<N>
+    for std::collections::HashSet<N, S>
+{
+    fn insert(&mut self, node: &N) -> bool {
+        std::collections::HashSet::insert(self, node.clone())
+    }
+
+    fn contains(&self, node: &N) -> bool {
+        std::collections::HashSet::contains(self, node)
+    }
+}
+
+/// Positions are visited once their cell is `true`.
+///
+/// Panics on positions outside the grid.
+impl Visited<Position> for Grid<bool> {
+    fn insert(&mut self, &node: &Position) -> bool {
+        !core::mem::replace(&mut self[node], true)
+    }
+
+    fn contains(&self, &node: &Position) -> bool {
+        self[node]
+    }
+}
+
+/// Treats every node as new, so a search follows every path rather than
+/// every node. Only finishes if the graph has no cycles or `visit` prunes
+/// them.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NeverVisited;
+
+impl<N> Visited<N> for NeverVisited {
+    fn insert(&mut self, _node: &N) -> bool {
+        true
+    }
+
+    fn contains(&self, _node: &N) -> bool {
+        false
+    }
+}
+
+/// What a search should do after visiting a node
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Control {
+    /// Carry on to the node's successors
+    Continue,
+    /// Skip the node's successors but carry on with the rest of the search
+    Prune,
+    /// End the search here
+    Stop,
+}
+
+impl Control {
+    /// [Control::Stop] once `done`, otherwise [Control::Continue]
+    pub fn stop_if(done: bool) -> Self {
+        match done {
+            true => Control::Stop,
+            false => Control::Continue,
+        }
+    }
+}
+
+/// A path found by a search
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// Nodes from the start to the end, including both
+    pub nodes: Vec<N>,
+    /// Sum of the cost of each step, so zero for just the start
+    pub cost: C,
+}
+
+/// A node reached by a search, along with how it got there
+pub struct Step<'a, N, C> {
+    node: &'a N,
+    cost: C,
+    depth: usize,
+    trail: Trail<'a, N, C>,
+}
+
+/// Where the rest of a [Step]'s path is kept
+enum Trail<'a, N, C> {
+    /// Index of the node's parent in the arena, if it has one
+    Arena(&'a [Entry<N, C>], Option<usize>),
+    /// The nodes before this one on the depth first stack
+    Stack(&'a [Frame<N, C>]),
+}
+
+impl<'a, N: Clone, C: Copy> Step<'a, N, C> {
+    pub fn node(&self) -> &'a N {
+        self.node
+    }
+
+    /// Cost of the path from the start to this node
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// Number of steps from the start to this node
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Nodes from the start up to and including this one
+    pub fn path(&self) -> Vec<N> {
+        let mut nodes = match self.trail {
+            Trail::Arena(arena, parent) => arena_path(arena, parent),
+            Trail::Stack(stack) => stack.iter().map(|frame| frame.node.clone()).collect(),
+        };
+        nodes.push(self.node.clone());
+        nodes
+    }
+
+    fn into_path(self) -> Path<N, C> {
+        Path {
+            nodes: self.path(),
+            cost: self.cost,
+        }
+    }
+}
+
+/// A node found by the breadth first and best first searches, which keep
+/// every node found so that paths can be followed back to the start
+struct Entry<N, C> {
+    node: N,
+    parent: Option<usize>,
+    cost: C,
+    depth: usize,
+}
+
+impl<N, C: Copy> Entry<N, C> {
+    fn step<'a>(arena: &'a [Self], index: usize) -> Step<'a, N, C> {
+        let entry = &arena[index];
+        Step {
+            node: &entry.node,
+            cost: entry.cost,
+            depth: entry.depth,
+            trail: Trail::Arena(arena, entry.parent),
+        }
+    }
+}
+
+/// Nodes from the start to `last`, following parents back through the arena
+fn arena_path<N: Clone, C>(arena: &[Entry<N, C>], mut last: Option<usize>) -> Vec<N> {
+    let mut nodes = Vec::new();
+    while let Some(index) = last {
+        nodes.push(arena[index].node.clone());
+        last = arena[index].parent;
+    }
+    nodes.reverse();
+    nodes
+}
+
+/// A node on the depth first stack with the successors still to explore,
+/// in reverse order
+struct Frame<N, C> {
+    node: N,
+    cost: C,
+    successors: Vec<(N, C)>,
+}
+
+/// Visit nodes in order of the number of steps from `start`, so the first
+/// path to each node has the fewest steps.
+///
+/// Nodes are marked visited as they're found, and `start` is always visited.
+/// Returns the path to the node where `visit` returned [Control::Stop].
+pub fn bfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    visited: &mut impl Visited<G::Node>,
+    mut visit: impl FnMut(&Step<G::Node, G::Cost>) -> Control,
+) -> Option<Path<G::Node, G::Cost>> {
+    visited.insert(&start);
+    let mut arena = vec![Entry {
+        node: start,
+        parent: None,
+        cost: G::Cost::ZERO,
+        depth: 0,
+    }];
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        let step = Entry::step(&arena, index);
+        match visit(&step) {
+            Control::Continue => {}
+            Control::Prune => continue,
+            Control::Stop => return Some(step.into_path()),
+        }
+
+        let Entry {
+            node, cost, depth, ..
+        } = &arena[index];
+        let (node, cost, depth) = (node.clone(), *cost, *depth);
+        for (next, step_cost) in graph.successors(&node) {
+            if visited.insert(&next) {
+                arena.push(Entry {
+                    node: next,
+                    parent: Some(index),
+                    cost: cost + step_cost,
+                    depth: depth + 1,
+                });
+                queue.push_back(arena.len() - 1);
+            }
+        }
+    }
+    None
+}
+
+/// Visit nodes going as deep as possible before backtracking, taking
+/// successors in the order the graph lists them.
+///
+/// Nodes are marked visited as they're entered, and `start` is always
+/// visited. Only the current path is
+/// kept, so with [NeverVisited] this enumerates every path without holding
+/// them all. Returns the path to the node where `visit` returned
+/// [Control::Stop].
+pub fn dfs<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    visited: &mut impl Visited<G::Node>,
+    mut visit: impl FnMut(&Step<G::Node, G::Cost>) -> Control,
+) -> Option<Path<G::Node, G::Cost>> {
+    visited.insert(&start);
+    let mut stack: Vec<Frame<G::Node, G::Cost>> = Vec::new();
+    let mut next = Some((start, G::Cost::ZERO));
+
+    loop {
+        if let Some((node, cost)) = next.take() {
+            let step = Step {
+                node: &node,
+                cost,
+                depth: stack.len(),
+                trail: Trail::Stack(&stack),
+            };
+            match visit(&step) {
+                Control::Continue => {
+                    let mut successors: Vec<_> = graph.successors(&node).collect();
+                    successors.reverse();
+                    stack.push(Frame {
+                        node,
+                        cost,
+                        successors,
+                    });
+                }
+                Control::Prune => {}
+                Control::Stop => return Some(step.into_path()),
+            }
+        }
+
+        let frame = stack.last_mut()?;
+        match frame.successors.pop() {
+            Some((node, step_cost)) => {
+                if visited.insert(&node) {
+                    next = Some((node, frame.cost + step_cost));
+                }
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+}
+
+/// Visit nodes in order of their lowest cost from `start`, so each node is
+/// first visited along its cheapest path.
+///
+/// Nodes are marked visited once they're visited, when their cost is known.
+/// Returns the path to the node where `visit` returned [Control::Stop].
+pub fn dijkstra<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    visited: &mut impl Visited<G::Node>,
+    visit: impl FnMut(&Step<G::Node, G::Cost>) -> Control,
+) -> Option<Path<G::Node, G::Cost>> {
+    astar(graph, start, visited, |_| G::Cost::ZERO, visit)
+}
+
+/// [dijkstra] guided towards a goal. `heuristic` estimates the cost from a
+/// node to the goal; as long as it never overestimates, the node `visit`
+/// stops at is still reached along its cheapest path.
+pub fn astar<G: Graph>(
+    graph: &G,
+    start: G::Node,
+    visited: &mut impl Visited<G::Node>,
+    heuristic: impl Fn(&G::Node) -> G::Cost,
+    mut visit: impl FnMut(&Step<G::Node, G::Cost>) -> Control,
+) -> Option<Path<G::Node, G::Cost>> {
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut arena = vec![Entry {
+        node: start,
+        parent: None,
+        cost: G::Cost::ZERO,
+        depth: 0,
+    }];
+
+    // Ties go to the node found first
+    while let Some(Reverse((_, index))) = heap.pop() {
+        if !visited.insert(&arena[index].node) {
+            // Already reached along a cheaper path
+            continue;
+        }
+        let step = Entry::step(&arena, index);
+        match visit(&step) {
+            Control::Continue => {}
+            Control::Prune => continue,
+            Control::Stop => return Some(step.into_path()),
+        }
+
+        let Entry {
+            node, cost, depth, ..
+        } = &arena[index];
+        let (node, cost, depth) = (node.clone(), *cost, *depth);
+        for (next, step_cost) in graph.successors(&node) {
+            if visited.contains(&next) {
+                continue;
+            }
+            let cost = cost + step_cost;
+            heap.push(Reverse((cost + heuristic(&next), arena.len())));
+            arena.push(Entry {
+                node: next,
+                parent: Some(index),
+                cost,
+                depth: depth + 1,
+            });
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Edges as `(from, to, cost)`
+    struct Edges(&'static [(u8, u8, u32)]);
+
+    impl Graph for Edges {
+        type Node = u8;
+        type Cost = u32;
+
+        fn successors(&self, &node: &u8) -> impl Iterator<Item = (u8, u32)> {
+            self.0
+                .iter()
+                .filter(move |edge| edge.0 == node)
+                .map(|&(_, to, cost)| (to, cost))
+        }
+    }
+
+    //   0 -1-> 1 -1-> 2 -1-> 3
+    //   |             ^
+    //   +------5------+
+    const LINE: Edges = Edges(&[(0, 1, 1), (0, 2, 5), (1, 2, 1), (2, 3, 1), (3, 0, 1)]);
+
+    fn order(
+        search: impl FnOnce(&mut dyn FnMut(&Step<u8, u32>) -> Control),
+    ) -> Vec<(u8, u32, usize)> {
+        let mut seen = Vec::new();
+        search(&mut |step| {
+            seen.push((*step.node(), step.cost(), step.depth()));
+            Control::Continue
+        });
+        seen
+    }
+
+    #[test]
+    fn visit_orders() {
+        assert_eq!(
+            order(|visit| {
+                bfs(&LINE, 0, &mut BTreeSet::new(), visit);
+            }),
+            [(0, 0, 0), (1, 1, 1), (2, 5, 1), (3, 6, 2)]
+        );
+        assert_eq!(
+            order(|visit| {
+                dfs(&LINE, 0, &mut BTreeSet::new(), visit);
+            }),
+            [(0, 0, 0), (1, 1, 1), (2, 2, 2), (3, 3, 3)]
+        );
+        assert_eq!(
+            order(|visit| {
+                dijkstra(&LINE, 0, &mut BTreeSet::new(), visit);
+            }),
+            [(0, 0, 0), (1, 1, 1), (2, 2, 2), (3, 3, 3)]
+        );
+    }
+
+    #[test]
+    fn stop_returns_path() {
+        let to_3 = |step: &Step<u8, u32>| Control::stop_if(*step.node() == 3);
+        let expected = Path {
+            nodes: vec![0, 1, 2, 3],
+            cost: 3,
+        };
+        assert_eq!(
+            dijkstra(&LINE, 0, &mut BTreeSet::new(), to_3),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            astar(&LINE, 0, &mut BTreeSet::new(), |&x| 3 - x as u32, to_3),
+            Some(expected.clone())
+        );
+        assert_eq!(dfs(&LINE, 0, &mut BTreeSet::new(), to_3), Some(expected));
+        assert_eq!(
+            bfs(&LINE, 0, &mut BTreeSet::new(), to_3),
+            Some(Path {
+                nodes: vec![0, 2, 3],
+                cost: 6,
+            })
+        );
+        assert_eq!(
+            bfs(&LINE, 0, &mut BTreeSet::new(), |step| {
+                Control::stop_if(*step.node() == 4)
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn prune_and_every_path() {
+        // Every path from 0 that stops at 3 rather than going round again
+        let mut paths = Vec::new();
+        dfs(&LINE, 0, &mut NeverVisited, |step| match step.node() {
+            3 => {
+                paths.push(step.path());
+                Control::Prune
+            }
+            _ => Control::Continue,
+        });
+        assert_eq!(paths, [vec![0, 1, 2, 3], vec![0, 2, 3]]);
+
+        // Pruning 1 leaves only the expensive way round
+        let found = dijkstra(&LINE, 0, &mut BTreeSet::new(), |step| match step.node() {
+            1 => Control::Prune,
+            &x => Control::stop_if(x == 3),
+        });
+        assert_eq!(found.map(|x| x.cost), Some(6));
+    }
+
+    #[test]
+    fn grid_visited() {
+        struct Open(Grid<char>);
+
+        impl Graph for Open {
+            type Node = Position;
+            type Cost = usize;
+
+            fn successors(&self, &node: &Position) -> impl Iterator<Item = (Position, usize)> {
+                self.0
+                    .neighbors4(node)
+                    .filter(|&x| self.0[x] == '.')
+                    .map(|x| (x, 1))
+            }
+        }
+
+        let maze = Open(Grid::from_chars("..#\n#..\n..#").unwrap());
+        let mut visited = Grid::new(3, 3, false);
+        let found = bfs(&maze, (0, 0), &mut visited, |step| {
+            Control::stop_if(*step.node() == (2, 0))
+        });
+        assert_eq!(
+            found.unwrap().nodes,
+            [(0, 0), (0, 1), (1, 1), (2, 1), (2, 0)]
+        );
+        assert!(visited[(1, 2)]);
+        assert!(!visited[(0, 2)]);
+    }
+}
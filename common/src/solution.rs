@@ -0,0 +1,318 @@
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec;
+use core::any::Any;
+use core::fmt;
+use core::time::Duration;
+
+use crate::animation::Recorder;
+use crate::outline::Outline;
+use crate::random::Rng;
+use crate::render::Picture;
+use crate::{AocError, Json};
+
+/// One of the two halves of each day's puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+
+    /// Parse the `1` or `2` given on the command line
+    pub fn from_number(number: &str) -> Option<Self> {
+        match number.trim() {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None,
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The result of solving one part of a puzzle.
+///
+/// Most answers are numbers, but some (like day 13's folded letters) can only
+/// be read off a picture.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(x) => write!(f, "{x}"),
+            Answer::Text(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Number(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_number!(u8, u32, u64, usize, i32, i64);
+
+impl From<&Answer> for Json {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Number(x) => Json::Int(*x),
+            Answer::Text(x) => Json::String(x.clone()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+/// A day's puzzle solver.
+///
+/// The input text is parsed once and then handed to each part, so the parsed
+/// representation should hold everything both parts need. Malformed input is
+/// reported with an [AocError] rather than a panic.
+pub trait Solution {
+    /// Day of the advent calendar this solves
+    const DAY: u8;
+
+    /// Parsed form of the puzzle input
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+    fn part1(input: &Self::Input) -> Result<Answer, AocError>;
+    fn part2(input: &Self::Input) -> Result<Answer, AocError>;
+
+    fn solve(input: &Self::Input, part: Part) -> Result<Answer, AocError> {
+        match part {
+            Part::One => Self::part1(input),
+            Part::Two => Self::part2(input),
+        }
+    }
+
+    /// Structured details behind a part's answer for `--format json`, like
+    /// the winning bingo board rather than just its score
+    fn extras(_input: &Self::Input, _part: Part) -> Option<Json> {
+        None
+    }
+
+    /// Picture of the puzzle's state once the part is solved, for `--render`
+    fn picture(_input: &Self::Input, _part: Part) -> Option<Picture> {
+        None
+    }
+
+    /// Run the part's simulation recording a frame per step, for
+    /// `--animate`. Returns false for days that aren't simulations.
+    fn animate(_input: &Self::Input, _part: Part, _recorder: &mut dyn Recorder) -> bool {
+        false
+    }
+
+    /// Tree of what the part found, like day 16's packets, for browsing in
+    /// `aoc explore`
+    fn outline(_input: &Self::Input, _part: Part) -> Option<Outline> {
+        None
+    }
+
+    /// A random puzzle input for `aoc gen`, `size` lines, boards, packets or
+    /// whatever the day's input is made of, or about as big as the real input
+    /// for `None`
+    fn generate(_size: Option<usize>, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+}
+
+/// Object-safe view of a [Solution] so days can be picked at runtime.
+pub trait DynSolution {
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError>;
+
+    /// Solve using the value returned by [DynSolution::parse]
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Result<Answer, AocError>;
+
+    /// See [Solution::extras]
+    fn extras(&self, parsed: &dyn Any, part: Part) -> Option<Json>;
+
+    /// See [Solution::picture]
+    fn picture(&self, parsed: &dyn Any, part: Part) -> Option<Picture>;
+
+    /// See [Solution::animate]
+    fn animate(&self, parsed: &dyn Any, part: Part, recorder: &mut dyn Recorder) -> bool;
+
+    /// See [Solution::outline]
+    fn outline(&self, parsed: &dyn Any, part: Part) -> Option<Outline>;
+
+    /// See [Solution::generate]
+    fn generate(&self, size: Option<usize>, rng: &mut Rng) -> Option<String>;
+}
+
+struct Registered<S>(core::marker::PhantomData<S>);
+
+impl<S> DynSolution for Registered<S>
+where
+    S: Solution,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Result<Answer, AocError> {
+        S::solve(Self::input(parsed), part)
+    }
+
+    fn extras(&self, parsed: &dyn Any, part: Part) -> Option<Json> {
+        S::extras(Self::input(parsed), part)
+    }
+
+    fn picture(&self, parsed: &dyn Any, part: Part) -> Option<Picture> {
+        S::picture(Self::input(parsed), part)
+    }
+
+    fn animate(&self, parsed: &dyn Any, part: Part, recorder: &mut dyn Recorder) -> bool {
+        S::animate(Self::input(parsed), part, recorder)
+    }
+
+    fn outline(&self, parsed: &dyn Any, part: Part) -> Option<Outline> {
+        S::outline(Self::input(parsed), part)
+    }
+
+    fn generate(&self, size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        S::generate(size, rng)
+    }
+}
+
+impl<S: Solution> Registered<S>
+where
+    S::Input: 'static,
+{
+    fn input(parsed: &dyn Any) -> &S::Input {
+        parsed
+            .downcast_ref::<S::Input>()
+            .expect("parsed input handed to the wrong day")
+    }
+}
+
+/// Box up a [Solution] for runtime dispatch
+pub fn register<S>() -> Box<dyn DynSolution>
+where
+    S: Solution + 'static,
+    S::Input: 'static,
+{
+    Box::new(Registered::<S>(core::marker::PhantomData))
+}
+
+/// The `--format json` record for an answer
+pub fn answer_record(
+    day: u8,
+    part: Part,
+    answer: &Answer,
+    elapsed: Duration,
+    extras: Option<Json>,
+) -> Json {
+    let mut record = vec![
+        ("day", day.into()),
+        ("part", part.number().into()),
+        ("answer", answer.into()),
+        ("elapsed", elapsed.as_secs_f64().into()),
+    ];
+    if let Some(extras) = extras {
+        record.push(("extras", extras));
+    }
+    Json::object(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 1;
+        type Input = Vec<u32>;
+
+        fn parse(input: &str) -> Result<Self::Input, AocError> {
+            Ok(crate::parse::Span::new(input)
+                .lines()
+                .map(|x| x.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+            Ok(input.iter().sum::<u32>().into())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+            Ok(input.iter().product::<u32>().into())
+        }
+    }
+
+    #[test]
+    fn json_record() {
+        let record = answer_record(
+            4,
+            Part::Two,
+            &Answer::Number(4495),
+            Duration::from_millis(250),
+            Some(Json::object([("score", 4495.into())])),
+        );
+        assert_eq!(
+            record.to_string(),
+            r#"{"day":4,"part":2,"answer":4495,"elapsed":0.25,"extras":{"score":4495}}"#
+        );
+    }
+
+    #[test]
+    fn dispatch() {
+        let solution = register::<Sum>();
+        let parsed = solution.parse("2\n3\n4\n").unwrap();
+        assert_eq!(solution.day(), 1);
+        assert_eq!(
+            solution.solve(parsed.as_ref(), Part::One).unwrap(),
+            Answer::Number(9)
+        );
+        assert_eq!(
+            solution.solve(parsed.as_ref(), Part::Two).unwrap(),
+            Answer::Number(24)
+        );
+
+        let error = solution.parse("2\nx\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { ref error, .. } if error.line == 2));
+    }
+}
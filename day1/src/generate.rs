@@ -0,0 +1,17 @@
+//! Random sonar sweeps for `aoc gen`.
+
+use common::random::Rng;
+
+/// Measurements in the real input
+pub const SIZE: usize = 2000;
+
+/// `size` depths, one per line, mostly getting deeper like the real sea floor
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut depth: i64 = rng.range(100..=200);
+    let mut text = String::new();
+    for _ in 0..size {
+        text.push_str(&format!("{depth}\n"));
+        depth = (depth + rng.range(-12..=20)).max(0);
+    }
+    text
+}
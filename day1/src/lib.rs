@@ -0,0 +1,27 @@
+//! Day 1: Sonar Sweep. Counting how often the sea floor gets deeper.
+
+pub mod generate;
+
+use common::parse::{ParseError, Span};
+
+/// Parse one depth measurement per line
+pub fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(|val| val.trim().parse())
+        .collect()
+}
+
+/// Number of measurements deeper than the one before
+pub fn part1(data: &[i64]) -> u64 {
+    let mut increases: u64 = 0;
+    let mut old: i64 = i64::MAX;
+
+    for &val in data {
+        if val > old {
+            increases += 1;
+        }
+        old = val;
+    }
+    increases
+}
@@ -1,32 +1,21 @@
-fn main() {
-    // Read input file
-    //let string_data = match common::read_input("test_input.txt") {
-    let string_data = match common::read_input("input.txt") {
-        Ok(val) => val,
-        Err(_) => panic!("File not Found! PANIC!"),
-    };
-    println!("{:?}", string_data);
+use common::{Format, Part};
+use std::process::ExitCode;
+use std::time::Instant;
 
-    // Parse to numbers
-    let mut data: Vec<i64> = vec![];
-    for val in string_data {
-        match val.trim().parse() {
-            Ok(num) => data.push(num),
-            Err(err) => panic!("Can't processes number {}, got err {}", val, err),
-        };
-    }
-    println!("As numbers: {:?}", data);
-
-    // find number of increases
-    let mut increases: u64 = 0;
-    let mut old: i64 = i64::MAX;
-
-    for val in data {
-        if val > old {
-            increases += 1;
+fn main() -> ExitCode {
+    // Get input, pass `test` to use test_input.txt instead
+    common::run_main(1, |inputdata, options| {
+        let data = day1::parse(inputdata)?;
+        let start = Instant::now();
+        let increases = day1::part1(&data);
+        match options.format {
+            Format::Text => println!("Power increased {increases} times!"),
+            Format::Json => {
+                common::print_record(1, Part::One, &increases.into(), start.elapsed(), None)
+            }
         }
-        old = val;
-    }
-
-    println!("Power increased {} times!", increases);
+        // Nothing to draw or animate, so asking to is an error
+        options.render(|| None)?;
+        options.animate(|_| false)
+    })
 }
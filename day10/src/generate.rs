@@ -0,0 +1,70 @@
+//! Random navigation subsystems for `aoc gen`.
+
+use crate::{CLOSING, OPENING};
+use common::random::Rng;
+
+/// Lines in the real input
+pub const SIZE: usize = 100;
+
+/// Chunks nest at most this deep, keeping completion scores well inside a
+/// `u64`
+const MAX_DEPTH: usize = 20;
+
+/// A bracket that doesn't close a chunk of `kind`
+fn wrong_closing(kind: usize, rng: &mut Rng) -> char {
+    CLOSING[(kind + rng.range(1..=3)) % 4]
+}
+
+/// One line of about 100 brackets that's either corrupted or incomplete
+fn line(corrupted: bool, rng: &mut Rng) -> String {
+    let length: usize = rng.range(90..=110);
+    let corrupt_at = rng.below(length as u64) as usize;
+    let mut corrupt = corrupted;
+    let mut stack: Vec<usize> = Vec::new();
+    let mut text = String::new();
+
+    for i in 0..length {
+        let open = stack.is_empty() || (stack.len() < MAX_DEPTH && rng.chance(1, 2));
+        match stack.pop() {
+            Some(kind) if !open && corrupt && i >= corrupt_at => {
+                text.push(wrong_closing(kind, rng));
+                corrupt = false;
+            }
+            Some(kind) if !open => text.push(CLOSING[kind]),
+            popped => {
+                stack.extend(popped);
+                let kind = rng.below(4) as usize;
+                stack.push(kind);
+                text.push(OPENING[kind]);
+            }
+        }
+    }
+
+    // Lines that never got to their corruption, or closed every chunk, get
+    // one more bracket to make them corrupted or incomplete
+    let kind = match stack.last() {
+        Some(&kind) => kind,
+        None => {
+            let kind = rng.below(4) as usize;
+            text.push(OPENING[kind]);
+            kind
+        }
+    };
+    if corrupt {
+        text.push(wrong_closing(kind, rng));
+    }
+    text
+}
+
+/// `size` lines of nested chunks, an odd number of them incomplete and the
+/// rest corrupted, as the puzzle promises
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut corrupted: Vec<bool> = (0..size.max(1)).map(|_| rng.chance(1, 2)).collect();
+    if corrupted.iter().filter(|&&x| !x).count() % 2 == 0 {
+        corrupted[0] = !corrupted[0];
+    }
+    corrupted
+        .into_iter()
+        .map(|corrupted| line(corrupted, rng) + "\n")
+        .collect()
+}
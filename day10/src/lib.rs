@@ -0,0 +1,143 @@
+//! Day 10: Syntax Scoring. Finding corrupted and incomplete lines of
+//! brackets.
+
+pub mod generate;
+
+use common::parse::{ParseError, Span};
+use common::random::Rng;
+use common::{debug, trace, Answer, AocError, Solution};
+
+const OPENING: [char; 4] = ['(', '[', '{', '<'];
+const CLOSING: [char; 4] = [')', ']', '}', '>'];
+
+/// Result of checking every line of the navigation subsystem
+#[derive(Debug)]
+pub struct Syntax {
+    /// The first illegal closing char of each corrupted line
+    pub first_corrupted: Vec<char>,
+    /// The closing chars needed to finish each incomplete line
+    pub completions: Vec<Vec<char>>,
+}
+
+impl Syntax {
+    /// Total syntax error score of the corrupted lines
+    pub fn error_score(&self) -> u64 {
+        self.first_corrupted.iter().fold(0, |acc, x| {
+            acc + match x {
+                ')' => 3,
+                ']' => 57,
+                '}' => 1197,
+                '>' => 25137,
+                _ => 0,
+            }
+        })
+    }
+
+    /// Middle score of the completions. Fails if no line is incomplete, or if
+    /// a completion is too long for its score to fit in 64 bits.
+    pub fn middle_completion_score(&self) -> Result<u64, AocError> {
+        let mut scores: Vec<u64> = self
+            .completions
+            .iter()
+            .map(|completion| {
+                completion
+                    .iter()
+                    .try_fold(0_u64, |acc, x| {
+                        acc.checked_mul(5)?.checked_add(match x {
+                            ')' => 1,
+                            ']' => 2,
+                            '}' => 3,
+                            '>' => 4,
+                            _ => 0,
+                        })
+                    })
+                    .ok_or_else(|| {
+                        AocError::Semantic(format!(
+                            "a completion of {} brackets scores too much for 64 bits",
+                            completion.len()
+                        ))
+                    })
+            })
+            .collect::<Result<_, _>>()?;
+        scores.sort();
+
+        debug!("completion scores {scores:?}");
+        scores
+            .get(scores.len() / 2)
+            .copied()
+            .ok_or_else(|| AocError::Semantic("no incomplete lines".to_owned()))
+    }
+}
+
+/// Check every line, sorting them into corrupted and incomplete ones
+pub fn parse(input: &str) -> Result<Syntax, ParseError> {
+    let mut first_corrupted: Vec<char> = Vec::new();
+    let mut completions: Vec<Vec<char>> = Vec::new();
+
+    for line in Span::new(input).lines() {
+        let mut stack: Vec<char> = Vec::new();
+        for (x, span) in line.chars() {
+            match OPENING.contains(&x) {
+                true => stack.push(x),
+                false => {
+                    let closing = CLOSING
+                        .iter()
+                        .position(|&q| q == x)
+                        .ok_or_else(|| span.error("expected a bracket"))?;
+                    let val = match stack.pop() {
+                        Some(val) => val,
+                        None => return Err(span.error("expected an opening bracket")),
+                    };
+                    match OPENING.iter().position(|&q| q == val).unwrap() == closing {
+                        true => continue,
+                        false => {
+                            trace!("corrupted by {x}");
+                            first_corrupted.push(x);
+                            stack.clear(); // corrupted not considered for completions
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+        // if there's something still on the stack, it's incomplete. offer
+        // completion
+        if !stack.is_empty() {
+            let mut this_completion: Vec<char> = Vec::new();
+            while let Some(opening_char) = stack.pop() {
+                this_completion
+                    .push(CLOSING[OPENING.iter().position(|&x| x == opening_char).unwrap()]);
+            }
+            completions.push(this_completion);
+        }
+    }
+    debug!("completions {completions:?}");
+
+    Ok(Syntax {
+        first_corrupted,
+        completions,
+    })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Syntax;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(input.error_score().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(input.middle_completion_score()?.into())
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
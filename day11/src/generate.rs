@@ -0,0 +1,23 @@
+//! Random octopus grids for `aoc gen`.
+
+use crate::first_synchronized_step;
+use common::random::Rng;
+use common::{warn, Grid};
+
+/// Width and height of the real input
+pub const SIZE: usize = 10;
+
+/// A `size` by `size` grid of energy levels that synchronizes within
+/// [MAX_STEPS](crate::MAX_STEPS). Random grids nearly always do, but the odd
+/// one that doesn't is rolled again.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(1);
+    let energy = loop {
+        let energy = Grid::from_vec(size, (0..size * size).map(|_| rng.range(0..=9)).collect());
+        match first_synchronized_step(&energy) {
+            Ok(_) => break energy,
+            Err(_) => warn!("octopuses don't synchronize, trying another grid"),
+        }
+    };
+    energy.to_string() + "\n"
+}
@@ -0,0 +1,232 @@
+//! Day 11: Dumbo Octopus. Simulating octopuses that flash when full of
+//! energy.
+
+pub mod generate;
+
+use common::animation::Recorder;
+use common::grid::Position;
+use common::parse::{digit_grid, ParseError};
+use common::random::Rng;
+use common::render::{Cell, Color, Picture};
+use common::{debug, trace, Answer, AocError, Grid, Part, Solution};
+
+/// Part 2 gives up on octopuses that haven't all flashed at once by now
+pub const MAX_STEPS: u32 = 5000;
+
+/// An octopus' energy level and whether it flashed this step
+#[derive(Clone, Debug)]
+struct Node {
+    value: u32,
+    flashed: bool,
+}
+
+/// Add energy, queueing the octopus to flash once it's over 9
+fn inc_push(map: &mut Grid<Node>, stack: &mut Vec<Position>, position: Position) {
+    map[position].value += 1;
+    if map[position].value > 9 {
+        stack.push(position);
+    }
+}
+
+/// Flash unless it already has this step, energizing the neighbors
+fn flash(map: &mut Grid<Node>, stack: &mut Vec<Position>, position: Position) {
+    if map[position].flashed {
+        return;
+    }
+
+    // mark flashed
+    map[position].flashed = true;
+    trace!("{position:?} flashes");
+
+    // energize all 8 neighbors, including diagonals
+    for neighbor in map.neighbors8(position) {
+        inc_push(map, stack, neighbor);
+    }
+}
+
+/// Advance the octopuses one step, returning how many flashed.
+fn step(map: &mut Grid<Node>) -> u32 {
+    // in each step energy += 1, energy == 10 flashes && adjacent (inc diagonal)
+    // energy += 1 && adjacent == 9 flashes && only flash once per step, each
+    // energy >= 9 set to 0.
+    // ==>
+    // states:
+    // 1) increment all
+    // 2a) collect any nodes that will flash
+    // 2b) if node > 9: increment neighbor nodes; goto 2a; else: goto 3)
+    // 3) set any node > 9 to 0
+
+    let mut flashes: u32 = 0;
+    let mut work_stack: Vec<Position> = Vec::new();
+
+    // increment all
+    for position in map.positions() {
+        map[position].value += 1;
+        // collect any that will flash, assume none have flashed yet
+        if map[position].value >= 10 {
+            work_stack.push(position);
+        }
+    }
+
+    // flash and increment and reflash as-needed
+    while let Some(flash_position) = work_stack.pop() {
+        flash(map, &mut work_stack, flash_position);
+    }
+
+    for node in map.iter_mut() {
+        if node.value > 9 {
+            node.value = 0;
+            flashes += 1;
+            node.flashed = false; // clear old flash
+        }
+    }
+    debug!("{flashes} flashes");
+    flashes
+}
+
+/// The grid of octopuses as they step through time
+#[derive(Clone, Debug)]
+pub struct Octopuses {
+    map: Grid<Node>,
+}
+
+impl Octopuses {
+    /// Start from the energy level of each octopus
+    pub fn new(energy: &Grid<u32>) -> Self {
+        Octopuses {
+            map: energy.map(|&value| Node {
+                value,
+                flashed: false,
+            }),
+        }
+    }
+
+    /// Advance one step, returning how many flashed
+    pub fn step(&mut self) -> u32 {
+        step(&mut self.map)
+    }
+
+    /// Energy level of each octopus, 0 for those that just flashed
+    pub fn energy(&self) -> Grid<u32> {
+        self.map.map(|node| node.value)
+    }
+
+    /// Number of octopuses
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+/// The energy levels, one digit per octopus
+pub fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+    digit_grid(input)
+}
+
+/// Total flashes over the first `steps` steps
+pub fn flashes_after(energy: &Grid<u32>, steps: u32) -> u32 {
+    flashes_after_recorded(energy, steps, &mut ())
+}
+
+/// [flashes_after], recording the energy levels at the start and after each
+/// step
+pub fn flashes_after_recorded(energy: &Grid<u32>, steps: u32, recorder: &mut dyn Recorder) -> u32 {
+    let mut octopuses = Octopuses::new(energy);
+    recorder.frame(&|| energy_picture(&octopuses.energy()));
+    let mut flashes: u32 = 0;
+    for _ in 0..steps {
+        flashes += octopuses.step();
+        recorder.frame(&|| energy_picture(&octopuses.energy()));
+    }
+    flashes
+}
+
+/// First step on which every octopus flashes at once, which fails if that's
+/// not within [MAX_STEPS]
+pub fn first_synchronized_step(energy: &Grid<u32>) -> Result<u32, AocError> {
+    first_synchronized_step_recorded(energy, &mut ())
+}
+
+/// [first_synchronized_step], recording the energy levels at the start and
+/// after each step
+pub fn first_synchronized_step_recorded(
+    energy: &Grid<u32>,
+    recorder: &mut dyn Recorder,
+) -> Result<u32, AocError> {
+    let mut octopuses = Octopuses::new(energy);
+    recorder.frame(&|| energy_picture(&octopuses.energy()));
+    let size = octopuses.len() as u32;
+    for step_count in 1..=MAX_STEPS {
+        let flashes = octopuses.step();
+        recorder.frame(&|| energy_picture(&octopuses.energy()));
+        if flashes == size {
+            return Ok(step_count);
+        }
+    }
+    Err(AocError::Semantic(format!(
+        "the octopuses don't all flash at once within {MAX_STEPS} steps"
+    )))
+}
+
+/// Draw energy levels getting brighter towards 9, with the octopuses that
+/// just flashed in yellow
+pub fn energy_picture(energy: &Grid<u32>) -> Picture {
+    Picture::from_grid(energy, |&value| match value {
+        0 => Cell::new('0', Color::YELLOW),
+        _ => Cell::new(
+            char::from_digit(value.min(9), 10).unwrap(),
+            Color::BLUE.mix(Color::WHITE, value as f32 / 9.0),
+        ),
+    })
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    /// how many flashes after 100 steps?
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(flashes_after(input, 100).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(first_synchronized_step(input)?.into())
+    }
+
+    /// The octopuses after 100 steps, or as they all flash together
+    fn picture(input: &Self::Input, part: Part) -> Option<Picture> {
+        let steps = match part {
+            Part::One => 100,
+            Part::Two => first_synchronized_step(input).ok()?,
+        };
+        let mut octopuses = Octopuses::new(input);
+        for _ in 0..steps {
+            octopuses.step();
+        }
+        Some(energy_picture(&octopuses.energy()))
+    }
+
+    /// Each step up to the same point as [Solution::picture]
+    fn animate(input: &Self::Input, part: Part, recorder: &mut dyn Recorder) -> bool {
+        match part {
+            Part::One => {
+                flashes_after_recorded(input, 100, recorder);
+                true
+            }
+            Part::Two => first_synchronized_step_recorded(input, recorder).is_ok(),
+        }
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
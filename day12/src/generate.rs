@@ -0,0 +1,74 @@
+//! Random cave systems for `aoc gen`.
+
+use crate::MAX_SMALL_CAVES;
+use common::random::Rng;
+use std::collections::BTreeSet;
+
+/// Caves in the real input, besides `start` and `end`
+pub const SIZE: usize = 10;
+
+/// A cave name that isn't in `names` yet, two letters in the given case
+fn new_name(names: &[String], big: bool, rng: &mut Rng) -> String {
+    let first = if big { b'A' } else { b'a' };
+    loop {
+        let name: String = (0..2)
+            .map(|_| char::from(first + rng.below(26) as u8))
+            .collect();
+        if !names.contains(&name) {
+            return name;
+        }
+    }
+}
+
+/// `start`, `end` and `size` caves joined up so every cave can be reached,
+/// about a quarter of them big. Two big caves are never joined, since that
+/// would give endless paths.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let size = size.min(4 * (MAX_SMALL_CAVES - 2) / 3);
+    let mut names: Vec<String> = vec!["start".to_owned(), "end".to_owned()];
+    let mut big: Vec<bool> = vec![false, false];
+    for i in 0..size {
+        let is_big = i % 4 == 3;
+        names.push(new_name(&names, is_big, rng));
+        big.push(is_big);
+    }
+
+    let mut links: BTreeSet<(usize, usize)> = BTreeSet::new();
+    let mut link = |a: usize, b: usize| {
+        if a != b && !(big[a] && big[b]) {
+            links.insert((a.min(b), a.max(b)));
+        }
+    };
+
+    // Join each cave to one before it, then `end` to a couple of caves, so
+    // everything's connected
+    let mut order: Vec<usize> = (2..names.len()).collect();
+    rng.shuffle(&mut order);
+    for (i, &cave) in order.iter().enumerate() {
+        let earlier: Vec<usize> = order[..i]
+            .iter()
+            .copied()
+            .chain([0])
+            .filter(|&x| !(big[x] && big[cave]))
+            .collect();
+        link(cave, *rng.choose(&earlier).unwrap_or(&0));
+    }
+    let not_end: Vec<usize> = (0..names.len()).filter(|&x| x != 1).collect();
+    for _ in 0..rng.range(1..=2) {
+        link(1, *rng.choose(&not_end).unwrap_or(&0));
+    }
+    for _ in 0..size / 2 {
+        let (a, b) = (rng.below(names.len() as u64), rng.below(names.len() as u64));
+        link(a as usize, b as usize);
+    }
+
+    let mut lines: Vec<String> = links
+        .into_iter()
+        .map(|(a, b)| match rng.chance(1, 2) {
+            true => format!("{}-{}\n", names[a], names[b]),
+            false => format!("{}-{}\n", names[b], names[a]),
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
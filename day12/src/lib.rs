@@ -0,0 +1,223 @@
+//! Day 12: Passage Pathing. Counting the paths through a cave system.
+
+pub mod generate;
+
+use common::outline::Outline;
+use common::parse::{separated_pair, ParseError, Span};
+use common::random::Rng;
+use common::search::{dfs, Control, Graph, NeverVisited};
+use common::{Answer, AocError, Part, Solution};
+use std::collections::HashMap;
+
+/// Caves connected to each cave, lowercase names are small caves
+pub type Caves = HashMap<String, Vec<String>>;
+
+/// The most small caves [CaveSystem] can keep track of
+const MAX_SMALL_CAVES: usize = 64;
+
+/// The caves numbered so that a path can be tracked without any allocation
+struct CaveSystem<'a> {
+    names: Vec<&'a str>,
+    /// Bit for each small cave in [Trail::small_visited], 0 for big caves
+    small_bits: Vec<u64>,
+    links: Vec<Vec<usize>>,
+    start: usize,
+    end: usize,
+}
+
+/// How a path got to a cave
+#[derive(Clone, Copy, Debug)]
+struct Trail {
+    cave: usize,
+    small_visited: u64,
+    small_twice: bool, // consumable token to allow passing through small caves twice
+}
+
+impl<'a> CaveSystem<'a> {
+    /// `None` if there's no `start` or `end` cave. Panics if there are more
+    /// than [MAX_SMALL_CAVES] small caves.
+    fn new(adjacencies: &'a Caves) -> Option<Self> {
+        let names: Vec<&str> = adjacencies.keys().map(String::as_str).collect();
+        let index = |name: &str| names.iter().position(|&x| x == name);
+        assert!(
+            names.iter().filter(|x| is_small(x)).count() <= MAX_SMALL_CAVES,
+            "more than {MAX_SMALL_CAVES} small caves"
+        );
+        let mut small_count = 0;
+        let small_bits = names
+            .iter()
+            .map(|name| match is_small(name) {
+                true => {
+                    small_count += 1;
+                    1 << (small_count - 1)
+                }
+                false => 0,
+            })
+            .collect();
+        let links = names
+            .iter()
+            .map(|name| adjacencies[*name].iter().filter_map(|x| index(x)).collect())
+            .collect();
+        Some(CaveSystem {
+            start: index("start")?,
+            end: index("end")?,
+            names,
+            small_bits,
+            links,
+        })
+    }
+}
+
+impl Graph for CaveSystem<'_> {
+    type Node = Trail;
+    type Cost = u32;
+
+    fn successors(&self, trail: &Trail) -> impl Iterator<Item = (Trail, u32)> {
+        // Paths finish at the end
+        let links: &[usize] = match trail.cave == self.end {
+            true => &[],
+            false => &self.links[trail.cave],
+        };
+        let trail = *trail;
+        links.iter().filter_map(move |&cave| {
+            // can't re-enter start node
+            if cave == self.start {
+                return None;
+            }
+            let bit = self.small_bits[cave];
+            let mut next = Trail {
+                cave,
+                small_visited: trail.small_visited | bit,
+                small_twice: trail.small_twice,
+            };
+            if trail.small_visited & bit != 0 {
+                // consume freepass to double back once on small caves,
+                // otherwise we can't push on
+                if !trail.small_twice {
+                    return None;
+                }
+                next.small_twice = false;
+            }
+            Some((next, 1))
+        })
+    }
+}
+
+fn is_small(cave: &str) -> bool {
+    cave.chars().all(|x| x.is_lowercase())
+}
+
+/// Every path from `start` to `end` that visits small caves at most once, or
+/// one of them twice when `small_twice` is set
+///
+/// Panics if there are more than 64 small caves.
+pub fn find_paths(adjacencies: &Caves, small_twice: bool) -> Vec<Vec<String>> {
+    let mut paths: Vec<Vec<String>> = Vec::new();
+    walk(adjacencies, small_twice, |system, trails| {
+        paths.push(
+            trails()
+                .iter()
+                .map(|x| system.names[x.cave].to_owned())
+                .collect(),
+        )
+    });
+    paths
+}
+
+/// Number of paths found by [find_paths]
+pub fn count_paths(adjacencies: &Caves, small_twice: bool) -> usize {
+    let mut count = 0;
+    walk(adjacencies, small_twice, |_, _| count += 1);
+    count
+}
+
+/// Depth first search calling `found` for each completed path, with a
+/// function to get the path
+fn walk(
+    adjacencies: &Caves,
+    small_twice: bool,
+    mut found: impl FnMut(&CaveSystem, &dyn Fn() -> Vec<Trail>),
+) {
+    // A missing start or end cave has no paths rather than panicking
+    let Some(system) = CaveSystem::new(adjacencies) else {
+        return;
+    };
+    let start = Trail {
+        cave: system.start,
+        small_visited: system.small_bits[system.start],
+        small_twice,
+    };
+    dfs(&system, start, &mut NeverVisited, |step| {
+        if step.node().cave == system.end {
+            found(&system, &|| step.path());
+        }
+        Control::Continue
+    });
+}
+
+/// One connection like `start-A` per line, caves are connected both ways and
+/// a connection given twice is still one way through. Two big caves can't be
+/// connected, there would be endless paths between them.
+pub fn parse(input: &str) -> Result<Caves, ParseError> {
+    let mut adjacencies: Caves = HashMap::new();
+
+    // read data into adjacencies map
+    for line in Span::new(input).lines() {
+        let (this_node, that_node) = separated_pair(line, "-")?;
+        let (this_node, that_node) = (this_node.as_str(), that_node.as_str());
+        if !is_small(this_node) && !is_small(that_node) {
+            return Err(line.error("expected a small cave at one end, not two big caves"));
+        }
+        let this_links = adjacencies
+            .entry(this_node.to_owned()) // grab entry if exists
+            .or_default(); // else create new empty entry
+        if this_links.iter().any(|x| x == that_node) {
+            continue;
+        }
+        this_links.push(that_node.to_owned()); // and that_node to list of connected nodes
+
+        // push everything in the other way because graph is not directional
+        adjacencies
+            .entry(that_node.to_owned()) // grab entry if exists
+            .or_default() // else create new empty entry
+            .push(this_node.to_owned()); // and that_node to list of connected nodes
+    }
+
+    if adjacencies.keys().filter(|x| is_small(x)).count() > MAX_SMALL_CAVES {
+        return Err(Span::new(input).error(format!("more than {MAX_SMALL_CAVES} small caves")));
+    }
+    Ok(adjacencies)
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Caves;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(count_paths(input, false).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(count_paths(input, true).into())
+    }
+
+    /// Every path the part counts
+    fn outline(input: &Self::Input, part: Part) -> Option<Outline> {
+        let paths: Vec<Outline> = find_paths(input, part == Part::Two)
+            .iter()
+            .map(|path| Outline::leaf(path.join(",")))
+            .collect();
+        Some(Outline::new(format!("{} paths", paths.len()), paths))
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
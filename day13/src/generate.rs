@@ -0,0 +1,81 @@
+//! Random transparent paper for `aoc gen`.
+
+use common::random::Rng;
+
+/// Folds in the real input
+pub const SIZE: usize = 12;
+
+/// Letters of the code, each 4 dots wide and 6 high with a space after it
+const LETTERS: usize = 8;
+
+/// The letters the real inputs use, in the font the puzzle draws them with
+const FONT: [(char, [&str; 6]); 14] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// The dots of a random eight letter code, then unfolded `size` times, each
+/// dot ending up on one side of the fold or both. Folding it back up shows
+/// the code again.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut dots: Vec<(u32, u32)> = Vec::new();
+    for i in 0..LETTERS {
+        let (_, rows) = rng.choose(&FONT).expect("the font has letters");
+        for (y, row) in rows.iter().enumerate() {
+            for (x, dot) in row.chars().enumerate() {
+                if dot == '#' {
+                    dots.push(((i * 5 + x) as u32, y as u32));
+                }
+            }
+        }
+    }
+
+    // Unfold from the last fold back to the first, the paper growing to twice
+    // as big plus the line folded along
+    let (mut width, mut height): (u32, u32) = (LETTERS as u32 * 5 - 1, 6);
+    let mut folds: Vec<String> = Vec::new();
+    for _ in 0..size.max(1) {
+        let along_x = rng.chance(1, 2);
+        let location = if along_x { width } else { height };
+        let unfold = |x: u32| 2 * location - x;
+        dots = dots
+            .into_iter()
+            .flat_map(|(x, y)| {
+                let mirrored = match along_x {
+                    true => (unfold(x), y),
+                    false => (x, unfold(y)),
+                };
+                match rng.below(4) {
+                    0 => vec![(x, y), mirrored],
+                    1 => vec![(x, y)],
+                    _ => vec![mirrored],
+                }
+            })
+            .collect();
+        match along_x {
+            true => width = 2 * width + 1,
+            false => height = 2 * height + 1,
+        }
+        folds.push(format!(
+            "fold along {}={location}\n",
+            if along_x { 'x' } else { 'y' }
+        ));
+    }
+    folds.reverse();
+    rng.shuffle(&mut dots);
+
+    let dots: String = dots.iter().map(|(x, y)| format!("{x},{y}\n")).collect();
+    dots + "\n" + &folds.concat()
+}
@@ -0,0 +1,286 @@
+//! Day 13: Transparent Origami. Folding paper to read the code in its dots.
+
+pub mod generate;
+
+use common::animation::Recorder;
+use common::parse::{sections, separated_pair, ParseError, Span};
+use common::random::Rng;
+use common::render::{Cell, Color, Picture};
+use common::{debug, Answer, AocError, Grid, Json, Part, Solution};
+
+/// A dot on the transparent paper, `x` to the right and `y` down
+#[derive(Debug, Clone, Copy)]
+pub struct Dot {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl PartialEq for Dot {
+    fn eq(&self, other: &Self) -> bool {
+        (self.x == other.x) && (self.y == other.y)
+    }
+}
+
+/// The axis a fold runs along, `X` folds the right half over to the left and
+/// `Y` the bottom half up
+#[derive(Debug)]
+pub enum FoldDirection {
+    X,
+    Y,
+}
+
+impl FoldDirection {
+    /// The last char of `fold along x`
+    fn from_text(string: &str) -> Option<Self> {
+        match string.chars().last() {
+            Some('x') => Some(FoldDirection::X),
+            Some('y') => Some(FoldDirection::Y),
+            Some(_) => None,
+            None => None,
+        }
+    }
+}
+
+/// One fold instruction, like `fold along y=7`
+#[derive(Debug)]
+pub struct Fold {
+    pub direction: FoldDirection,
+    pub location: u32,
+}
+
+/// Most cells a drawing of the dots can have, any more and they're too
+/// spread out to read
+const MAX_CELLS: usize = 1 << 24;
+
+impl Dot {
+    /// Move the dot to where it lands after the fold.
+    ///
+    /// Fails when the dot is further past the fold than the fold is from the
+    /// edge, so it would land off the paper.
+    pub fn fold(&mut self, fold: &Fold) -> Result<(), AocError> {
+        let (coordinate, axis) = match fold.direction {
+            FoldDirection::X => (&mut self.x, 'x'),
+            FoldDirection::Y => (&mut self.y, 'y'),
+        };
+        if *coordinate >= fold.location {
+            // (fold.location - 1) - (coordinate - (fold.location + 1))
+            // simplifies to:
+            *coordinate = fold
+                .location
+                .checked_mul(2)
+                .and_then(|x| x.checked_sub(*coordinate))
+                .ok_or_else(|| {
+                    AocError::Semantic(format!(
+                        "a dot at {axis}={coordinate} lands off the paper folding along {axis}={}",
+                        fold.location
+                    ))
+                })?;
+        }
+        Ok(())
+    }
+}
+
+/// The transparent paper and its folding instructions
+#[derive(Debug)]
+pub struct Manual {
+    pub dots: Vec<Dot>,
+    pub folds: Vec<Fold>,
+}
+
+/// Fold every dot along the line, dropping any that land on top of each other
+pub fn fold_dots(dots: &[Dot], fold: &Fold) -> Result<Vec<Dot>, AocError> {
+    debug!("folding {fold:?}");
+    let mut new_dots: Vec<Dot> = Vec::new();
+    for dot in dots {
+        let mut dot = *dot;
+        dot.fold(fold)?;
+        // Deduplicate
+        if !new_dots.contains(&dot) {
+            new_dots.push(dot);
+        }
+    }
+    debug!("{} dots left", new_dots.len());
+    Ok(new_dots)
+}
+
+/// Width and height of a drawing reaching the furthest dots.
+///
+/// Fails when there are no dots, or the drawing would have more than
+/// [MAX_CELLS] cells.
+fn drawing_size(dots: &[Dot]) -> Result<(usize, usize), AocError> {
+    let size = |coordinate: fn(&Dot) -> u32| {
+        let max = dots.iter().map(coordinate).max()?;
+        usize::try_from(max).ok()?.checked_add(1)
+    };
+    let (width, height) = match (size(|dot| dot.x), size(|dot| dot.y)) {
+        (Some(width), Some(height)) => (width, height),
+        _ if dots.is_empty() => return Err(AocError::Semantic("no dots to draw".to_owned())),
+        _ => return Err(AocError::Semantic("dots too far out to draw".to_owned())),
+    };
+    match width.checked_mul(height) {
+        Some(cells) if cells <= MAX_CELLS => Ok((width, height)),
+        _ => Err(AocError::Semantic(format!(
+            "dots spread over {width}x{height}, too big to draw"
+        ))),
+    }
+}
+
+/// Draw the dots with '#' on a blank background
+pub fn display(dots: &[Dot]) -> Result<String, AocError> {
+    let (width, height) = drawing_size(dots)?;
+    let mut display: Grid<char> = Grid::new(width, height, ' ');
+    for dot in dots {
+        display[(dot.y as usize, dot.x as usize)] = '#';
+    }
+    Ok(display.to_string())
+}
+
+/// Draw the dots in white on a blank background
+pub fn dot_picture(dots: &[Dot]) -> Result<Picture, AocError> {
+    let (width, height) = drawing_size(dots)?;
+    let mut picture = Picture::new(width, height, Cell::new(' ', Color::BACKGROUND));
+    for dot in dots {
+        picture.cells[(dot.y as usize, dot.x as usize)] = Cell::new('#', Color::WHITE);
+    }
+    Ok(picture)
+}
+
+/// Dots after the first `count` folds
+pub fn folded(manual: &Manual, count: usize) -> Result<Vec<Dot>, AocError> {
+    folded_recorded(manual, count, &mut ())
+}
+
+/// [folded], recording the paper before folding and after each fold. Paper
+/// too big to draw is left out.
+pub fn folded_recorded(
+    manual: &Manual,
+    count: usize,
+    recorder: &mut dyn Recorder,
+) -> Result<Vec<Dot>, AocError> {
+    let mut record = |dots: &[Dot]| {
+        if drawing_size(dots).is_ok() {
+            recorder.frame(&|| dot_picture(dots).expect("the size was checked"));
+        }
+    };
+    record(&manual.dots);
+    let mut dots = manual.dots.clone();
+    for fold in manual.folds.iter().take(count) {
+        dots = fold_dots(&dots, fold)?;
+        record(&dots);
+    }
+    Ok(dots)
+}
+
+/// Dot coordinates, then the fold instructions after a blank line
+pub fn parse(input: &str) -> Result<Manual, ParseError> {
+    let (dot_lines, fold_lines) = match sections(input)[..] {
+        [dots, folds] => (dots, folds),
+        _ => return Err(Span::new(input).error("expected dots and folds")),
+    };
+
+    let mut dots: Vec<Dot> = Vec::new();
+    for line in dot_lines.lines() {
+        let (x, y) = separated_pair(line, ",")?;
+        dots.push(Dot {
+            x: x.parse()?,
+            y: y.parse()?,
+        });
+    }
+
+    let mut folds: Vec<Fold> = Vec::new();
+    for line in fold_lines.lines() {
+        let (direction, location) = separated_pair(line, "=")?;
+        folds.push(Fold {
+            direction: FoldDirection::from_text(direction.as_str())
+                .ok_or_else(|| direction.error("expected \"fold along x\" or \"fold along y\""))?,
+            location: location.parse()?,
+        });
+    }
+    Ok(Manual { dots, folds })
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Manual;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    /// Dots visible after the first fold
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        match input.folds.first() {
+            Some(fold) => Ok(fold_dots(&input.dots, fold)?.len().into()),
+            None => Err(AocError::Semantic("no fold instructions".to_owned())),
+        }
+    }
+
+    /// The code spelled out once every fold is done
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(display(&folded(input, input.folds.len())?)?.into())
+    }
+
+    /// Coordinates of the dots left after the part's folds, as `[x, y]` pairs
+    fn extras(input: &Self::Input, part: Part) -> Option<Json> {
+        let folds = match part {
+            Part::One => 1,
+            Part::Two => input.folds.len(),
+        };
+        let mut dots: Vec<(u32, u32)> = folded(input, folds)
+            .ok()?
+            .iter()
+            .map(|dot| (dot.x, dot.y))
+            .collect();
+        dots.sort();
+        let dots: Vec<Vec<u32>> = dots.into_iter().map(|(x, y)| vec![x, y]).collect();
+        Some(Json::object([("dots", dots.into())]))
+    }
+
+    /// The paper after the part's folds
+    fn picture(input: &Self::Input, part: Part) -> Option<Picture> {
+        let folds = match part {
+            Part::One => 1,
+            Part::Two => input.folds.len(),
+        };
+        dot_picture(&folded(input, folds).ok()?).ok()
+    }
+
+    /// Each of the part's folds
+    fn animate(input: &Self::Input, part: Part, recorder: &mut dyn Recorder) -> bool {
+        let folds = match part {
+            Part::One => 1,
+            Part::Two => input.folds.len(),
+        };
+        folded_recorded(input, folds, recorder).is_ok()
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn folds_off_the_paper() {
+        let manual = parse("10,0\n1,1\n\nfold along x=2").unwrap();
+        assert!(Day13::part1(&manual).is_err());
+        assert!(Day13::part2(&manual).is_err());
+
+        let manual = parse("4,0\n0,1\n\nfold along x=2").unwrap();
+        assert_eq!(Day13::part1(&manual).unwrap(), Answer::Number(2));
+        assert_eq!(
+            Day13::part2(&manual).unwrap(),
+            Answer::Text("#\n#".to_owned())
+        );
+
+        let far = [Dot { x: u32::MAX, y: 0 }];
+        assert!(display(&far).is_err());
+        assert!(dot_picture(&[Dot { x: 5000, y: 5000 }]).is_err());
+        assert!(dot_picture(&[]).is_err());
+    }
+}
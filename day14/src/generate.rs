@@ -0,0 +1,27 @@
+//! Random polymer templates and rules for `aoc gen`.
+
+use common::random::Rng;
+
+/// Length of the real input's template
+pub const SIZE: usize = 20;
+
+/// Different elements in the real input
+const ELEMENTS: usize = 10;
+
+/// A template of `size` elements, then an insertion rule for every pair of
+/// the elements, like the real input
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut alphabet: Vec<char> = ('A'..='Z').collect();
+    rng.shuffle(&mut alphabet);
+    let elements = &alphabet[..ELEMENTS];
+    let mut pick = || *rng.choose(elements).expect("there are elements");
+
+    let template: String = (0..size.max(2)).map(|_| pick()).collect();
+    let mut text = template + "\n\n";
+    for &first in elements {
+        for &second in elements {
+            text.push_str(&format!("{first}{second} -> {}\n", pick()));
+        }
+    }
+    text
+}
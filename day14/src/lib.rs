@@ -0,0 +1,316 @@
+//! Day 14: Extended Polymerization. Growing a polymer by inserting elements
+//! between pairs.
+
+pub mod generate;
+
+use common::animation::Recorder;
+use common::parse::{sections, separated_pair, ParseError, Span};
+use common::random::Rng;
+use common::render::{log_bars, Color, Picture};
+use common::{debug, trace, Answer, AocError, Part, Solution};
+use std::collections::{BTreeSet, HashMap};
+
+/// Polymer template and pair insertion rules
+#[derive(Debug)]
+pub struct Polymer {
+    /// The starting chain of elements
+    pub template: String,
+    /// Element inserted between each pair, like `"CH" => 'B'`
+    pub patterns: HashMap<String, char>,
+}
+
+/// Find the difference between min and max values in map
+///
+/// @TODO, make generic for any key and any value that impls Ord (for cmp
+/// function)
+fn diff(histogram: &HashMap<char, u64>) -> u64 {
+    let min: u64 = histogram
+        .iter()
+        .min_by(|x, y| x.1.cmp(y.1))
+        .map(|(key, val)| {
+            debug!("least common {key}: {val}");
+            val
+        })
+        .unwrap()
+        .to_owned();
+    let max: u64 = histogram
+        .iter()
+        .max_by(|x, y| x.1.cmp(y.1))
+        .map(|(key, val)| {
+            debug!("most common {key}: {val}");
+            val
+        })
+        .unwrap()
+        .to_owned();
+
+    // Most - Least
+    max - min
+}
+
+/// Most - least common element after `steps` steps of insertion.
+///
+/// Naive implementation that builds the whole polymer. Exponential runtime,
+/// runtime blows up after 20 steps. See [spread_by_pairs] for more steps.
+pub fn spread_naive(template: &str, patterns: &HashMap<String, char>, steps: u32) -> u64 {
+    spread_naive_recorded(template, patterns, steps, &mut ())
+}
+
+/// [spread_naive], recording how common each element is at the start and
+/// after each step
+pub fn spread_naive_recorded(
+    template: &str,
+    patterns: &HashMap<String, char>,
+    steps: u32,
+    recorder: &mut dyn Recorder,
+) -> u64 {
+    let elements = elements(template, patterns);
+    let mut polymer: String = template.to_owned();
+    recorder.frame(&|| histogram_picture(&elements, &chain_histogram(&polymer)));
+    for _ in 0..steps {
+        let mut i: usize = 1;
+        loop {
+            if let Some(val) = patterns.get(&polymer[(i - 1)..(i + 1)]) {
+                polymer.insert(i, *val);
+                i += 1; // account for new char inserted
+            }
+            i += 1; // iterate
+            if i >= polymer.len() {
+                break;
+            }
+        }
+        recorder.frame(&|| histogram_picture(&elements, &chain_histogram(&polymer)));
+    }
+    diff(&chain_histogram(&polymer))
+}
+
+/// How many of each element are in the polymer
+fn chain_histogram(polymer: &str) -> HashMap<char, u64> {
+    polymer.chars().fold(HashMap::new(), |mut acc, x| {
+        *acc.entry(x).or_insert(0) += 1;
+        acc
+    })
+}
+
+/// Same as [spread_naive], but format string into pairs and count instances
+/// of each. this is faster because it reduces iterations by the number of
+/// instances of each pair. At 40 steps, there are billions of each pair.
+pub fn spread_by_pairs(template: &str, patterns: &HashMap<String, char>, steps: u32) -> u64 {
+    spread_by_pairs_recorded(template, patterns, steps, &mut ())
+}
+
+/// [spread_by_pairs], recording how common each element is at the start and
+/// after each step
+pub fn spread_by_pairs_recorded(
+    template: &str,
+    patterns: &HashMap<String, char>,
+    steps: u32,
+    recorder: &mut dyn Recorder,
+) -> u64 {
+    let elements = elements(template, patterns);
+    let mut pairs: HashMap<String, u64> = HashMap::new();
+
+    // process template into pairs
+    for i in 1..template.len() {
+        let pair: String = template[(i - 1)..(i + 1)].to_owned();
+        *pairs.entry(pair).or_insert(0) += 1;
+    }
+    recorder.frame(&|| histogram_picture(&elements, &pair_histogram(template, &pairs)));
+
+    for _ in 0..steps {
+        let mut new_pairs: HashMap<String, u64> = HashMap::new();
+        pairs
+            .iter()
+            .for_each(|(key, value)| match patterns.get(key) {
+                Some(newchar) => {
+                    let left: String = key[0..1].to_owned() + &(newchar.to_string());
+                    let right: String = newchar.to_string() + &key[1..2];
+                    *new_pairs.entry(left).or_insert(0) += value;
+                    *new_pairs.entry(right).or_insert(0) += value;
+                }
+                None => {
+                    *new_pairs.entry(key.to_owned()).or_insert(0) += value;
+                    // this probably could happen if patterns were poorly chosen
+                    trace!("no rule for {key}");
+                }
+            });
+        pairs = new_pairs;
+        recorder.frame(&|| histogram_picture(&elements, &pair_histogram(template, &pairs)));
+    }
+
+    diff(&pair_histogram(template, &pairs))
+}
+
+/// How many of each element are in a polymer grown from `template`, given
+/// the count of each pair in it
+fn pair_histogram(template: &str, pairs: &HashMap<String, u64>) -> HashMap<char, u64> {
+    let mut histogram: HashMap<char, u64> =
+        pairs.iter().fold(HashMap::new(), |mut acc, (key, value)| {
+            *acc.entry(key.chars().next().unwrap()).or_insert(0) += value;
+            acc
+        });
+
+    // add count for last char in template. It will always be last because we
+    // only insert between pairs and will be undercounted by 1 because we're
+    // only counting the first char in each pair.
+    *histogram
+        .entry(template.chars().last().unwrap())
+        .or_insert(0) += 1;
+
+    histogram
+}
+
+/// Every element that can be in the polymer, in order
+fn elements(template: &str, patterns: &HashMap<String, char>) -> Vec<char> {
+    let elements: BTreeSet<char> = template.chars().chain(patterns.values().copied()).collect();
+    elements.into_iter().collect()
+}
+
+/// A bar for each of `elements` on a log scale, since counts double each step
+pub fn histogram_picture(elements: &[char], histogram: &HashMap<char, u64>) -> Picture {
+    let bars: Vec<(u64, Color)> = elements
+        .iter()
+        .enumerate()
+        .map(|(i, element)| {
+            (
+                histogram.get(element).copied().unwrap_or(0),
+                Color::palette(i),
+            )
+        })
+        .collect();
+    log_bars(&bars, 48)
+}
+
+/// Whether `span` is all elements, which are ASCII letters so the solvers
+/// can slice pairs out by byte
+fn is_elements(span: Span) -> bool {
+    span.as_str().chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Template on the first line, then a blank line and the insertion rules
+pub fn parse(input: &str) -> Result<Polymer, ParseError> {
+    let (template, rules) = match sections(input)[..] {
+        [template, rules] => (template.trim(), rules),
+        _ => return Err(Span::new(input).error("expected a template and rules")),
+    };
+    if template.as_str().len() < 2 || !is_elements(template) {
+        return Err(template.error("expected a template of at least 2 elements, each a letter"));
+    }
+
+    let mut patterns: HashMap<String, char> = HashMap::new();
+    for line in rules.lines() {
+        let (pair, insert) = separated_pair(line, "->")?;
+        if pair.as_str().len() != 2 || !is_elements(pair) {
+            return Err(pair.error("expected a pair of elements, each a letter"));
+        }
+        let insert = match insert.as_str().chars().collect::<Vec<char>>()[..] {
+            [insert] if insert.is_ascii_alphabetic() => insert,
+            _ => return Err(insert.error("expected a single element, a letter")),
+        };
+        patterns.insert(pair.as_str().to_owned(), insert);
+    }
+
+    debug!("template {template}, {} rules", patterns.len());
+    trace!("rules {patterns:?}");
+    Ok(Polymer {
+        template: template.as_str().to_owned(),
+        patterns,
+    })
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Polymer;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    /// Most - Least after 10 steps
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(spread_naive(&input.template, &input.patterns, 10).into())
+    }
+
+    /// Most - Least after 40 steps
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(spread_by_pairs(&input.template, &input.patterns, 40).into())
+    }
+
+    /// The element counts at each step, the same way each part is solved
+    fn animate(input: &Self::Input, part: Part, recorder: &mut dyn Recorder) -> bool {
+        match part {
+            Part::One => spread_naive_recorded(&input.template, &input.patterns, 10, recorder),
+            Part::Two => spread_by_pairs_recorded(&input.template, &input.patterns, 40, recorder),
+        };
+        true
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::check::{differential, Shrink};
+    use common::random::Rng;
+
+    /// A polymer and how many steps to grow it
+    #[derive(Clone, Debug)]
+    struct Growth {
+        template: String,
+        patterns: HashMap<String, char>,
+        steps: u32,
+    }
+
+    /// Shorter templates and fewer steps, keeping the rules and at least the
+    /// one pair a template needs
+    impl Shrink for Growth {
+        fn shrink(&self) -> Vec<Self> {
+            let templates = self.template.shrink().into_iter().filter(|x| x.len() >= 2);
+            let templates = templates.map(|template| Growth {
+                template,
+                ..self.clone()
+            });
+            let steps = self.steps.shrink().into_iter().map(|steps| Growth {
+                steps,
+                ..self.clone()
+            });
+            templates.chain(steps).collect()
+        }
+    }
+
+    fn growth(rng: &mut Rng) -> Growth {
+        let polymer = parse(&generate::input(rng.range(2..=10), rng)).unwrap();
+        Growth {
+            template: polymer.template,
+            patterns: polymer.patterns,
+            steps: rng.range(0..=8),
+        }
+    }
+
+    #[test]
+    fn elements_are_letters() {
+        let column = |input| parse(input).unwrap_err().column;
+        assert_eq!(column("N\n\nNN -> C"), 1);
+        assert_eq!(column("NéN\n\nNN -> C"), 1);
+        assert_eq!(column("NN\n\nN -> C"), 1);
+        assert_eq!(column("NN\n\nNNN -> C"), 1);
+        assert_eq!(column("NN\n\nNN -> 7"), 7);
+        let polymer = parse("NN\n\nNN -> C").unwrap();
+        assert_eq!(spread_naive(&polymer.template, &polymer.patterns, 1), 1);
+    }
+
+    #[test]
+    fn pairs_match_growing_the_polymer() {
+        differential(
+            100,
+            14,
+            growth,
+            |x| spread_naive(&x.template, &x.patterns, x.steps),
+            |x| spread_by_pairs(&x.template, &x.patterns, x.steps),
+        );
+    }
+}
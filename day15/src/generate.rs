@@ -0,0 +1,16 @@
+//! Random cave risk levels for `aoc gen`.
+
+use common::random::Rng;
+
+/// Width and height of the real input
+pub const SIZE: usize = 100;
+
+/// A `size` by `size` map of risk levels from 1 to 9
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+    for _ in 0..size.max(1) {
+        text.extend((0..size.max(1)).map(|_| char::from(b'0' + rng.range(1u8..=9))));
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,152 @@
+//! Day 15: Chiton. Finding the lowest risk path through a cave.
+
+pub mod generate;
+
+use common::grid::Position;
+use common::parse::{digit_grid, ParseError};
+use common::random::Rng;
+use common::render::{Cell, Color, Picture};
+use common::search::{astar, Control, Graph, Path};
+use common::{debug, trace, Answer, AocError, Grid, Part, Solution};
+
+/// Moving up, down, left or right costs the risk level of the position
+/// entered
+struct Cave<'a>(&'a Grid<u32>);
+
+impl Graph for Cave<'_> {
+    type Node = Position;
+    type Cost = u32;
+
+    fn successors(&self, &position: &Position) -> impl Iterator<Item = (Position, u32)> {
+        self.0.neighbors4(position).map(|x| (x, self.0[x]))
+    }
+}
+
+/// Lowest risk path from `start` to `end`, moving up, down, left or right.
+/// The risk of the start isn't counted since it's never entered. `None` if
+/// either is off the map.
+pub fn shortest_path(
+    costmap: &Grid<u32>,
+    start: Position,
+    end: Position,
+) -> Option<Path<Position, u32>> {
+    if !costmap.contains(start) || !costmap.contains(end) {
+        return None;
+    }
+
+    // Every step costs at least the lowest risk on the map, so that times the
+    // distance never overestimates the risk left
+    let lowest = costmap.iter().copied().min().unwrap_or(0);
+    let distance =
+        |&(row, col): &Position| (row.abs_diff(end.0) + col.abs_diff(end.1)) as u32 * lowest;
+    let mut visited = Grid::new(costmap.width(), costmap.height(), false);
+    astar(&Cave(costmap), start, &mut visited, distance, |step| {
+        Control::stop_if(*step.node() == end)
+    })
+}
+
+/// Lowest risk path from the top left to the bottom right
+pub fn safest_path(costmap: &Grid<u32>) -> Option<Path<Position, u32>> {
+    let cm_rows = costmap.height();
+    let cm_cols = costmap.width();
+    debug!("rows {cm_rows}, cols {cm_cols}");
+    shortest_path(
+        costmap,
+        (0, 0),
+        (cm_rows.checked_sub(1)?, cm_cols.checked_sub(1)?),
+    )
+}
+
+/// Lowest total risk of any path from the top left to the bottom right
+pub fn lowest_risk(costmap: &Grid<u32>) -> Result<u32, AocError> {
+    safest_path(costmap)
+        .map(|path| path.cost)
+        .ok_or_else(|| AocError::Semantic("no path through the cave".to_owned()))
+}
+
+/// Draw the risk levels darker for riskier positions, with the path in red
+pub fn path_picture(costmap: &Grid<u32>, path: &[Position]) -> Picture {
+    let mut picture = Picture::from_grid(costmap, |&risk| {
+        Cell::new(
+            char::from_digit(risk.min(9), 10).unwrap(),
+            Color::WHITE.mix(Color::BACKGROUND, risk as f32 / 9.0),
+        )
+    });
+    for &position in path {
+        picture.cells[position].color = Color::RED;
+    }
+    picture
+}
+
+/// The full map is the input tiled 5 times in each direction, with the risk
+/// increasing by 1 for each tile away from the top left.
+pub fn tile(costmap: &Grid<u32>) -> Grid<u32> {
+    let costmap2 = costmap.tile(5, 5, |cost, (i, j)| {
+        let cost = cost + (i + j) as u32;
+        // Check if overflowed max of 9, can not overflow twice since only
+        // tiling 5 times
+        if cost > 9 {
+            cost - 9
+        } else {
+            cost
+        }
+    });
+    trace!("tiled map\n{costmap2}");
+    costmap2
+}
+
+/// The risk level of each position, one digit each
+pub fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+    digit_grid(input)
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(lowest_risk(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(lowest_risk(&tile(input))?.into())
+    }
+
+    /// The safest path through the map for the part
+    fn picture(input: &Self::Input, part: Part) -> Option<Picture> {
+        let costmap = match part {
+            Part::One => input.clone(),
+            Part::Two => tile(input),
+        };
+        let path = safest_path(&costmap)?;
+        Some(path_picture(&costmap, &path.nodes))
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_is_reconstructed() {
+        let map = parse(include_str!("../test_input.txt")).unwrap();
+        let path = safest_path(&map).unwrap();
+        assert_eq!(path.cost, 40);
+        assert_eq!(path.nodes.first(), Some(&(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&(9, 9)));
+        let entered: u32 = path.nodes[1..].iter().map(|&x| map[x]).sum();
+        assert_eq!(entered, path.cost);
+
+        assert_eq!(shortest_path(&map, (0, 0), (10, 0)), None);
+    }
+}
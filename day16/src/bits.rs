@@ -0,0 +1,127 @@
+//! Reading a BITS transmission a few bits at a time, straight from its bytes.
+
+/// Reads numbers of any width up to 64 bits from a slice of bytes, most
+/// significant bit first.
+///
+/// A reader can be limited to fewer bits than its bytes hold, like the
+/// subpackets of an operator with a total length, and keeps track of its
+/// position in the whole transmission so errors can point at the bit.
+#[derive(Clone, Debug)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    /// Bit offset of the next bit to read
+    position: usize,
+    /// Bit offset the reader stops at
+    end: usize,
+}
+
+impl<'a> BitReader<'a> {
+    /// Read all the bits of `bytes`
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BitReader {
+            bytes,
+            position: 0,
+            end: bytes.len() * 8,
+        }
+    }
+
+    /// Offset of the next bit from the start of the transmission
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Offset of the bit the reader stops at
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Bits left to read
+    pub fn remaining(&self) -> usize {
+        self.end - self.position
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// The next `width` bits as a number, or `None` without moving on when
+    /// there aren't that many left.
+    ///
+    /// Panics if `width` is more than 64.
+    pub fn read_bits(&mut self, width: usize) -> Option<u64> {
+        assert!(width <= 64, "can't read {width} bits into a u64");
+        if width > self.remaining() {
+            return None;
+        }
+
+        // A byte, or what's left of the current one, at a time
+        let mut value: u64 = 0;
+        let mut left = width;
+        while left > 0 {
+            let byte = self.bytes[self.position / 8];
+            let offset = self.position % 8;
+            let take = left.min(8 - offset);
+            let bits = (byte >> (8 - offset - take)) & (0xff >> (8 - take));
+            value = (value << take) | bits as u64;
+            self.position += take;
+            left -= take;
+        }
+        Some(value)
+    }
+
+    /// A reader for the next `len` bits, which this one skips over, or `None`
+    /// when there aren't that many left
+    pub fn sub_reader(&mut self, len: usize) -> Option<BitReader<'a>> {
+        if len > self.remaining() {
+            return None;
+        }
+        let sub = BitReader {
+            bytes: self.bytes,
+            position: self.position,
+            end: self.position + len,
+        };
+        self.position += len;
+        Some(sub)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_across_bytes() {
+        // 110100101111111000101000
+        let mut reader = BitReader::new(&[0xD2, 0xFE, 0x28]);
+        assert_eq!(reader.read_bits(3), Some(6));
+        assert_eq!(reader.read_bits(3), Some(4));
+        assert_eq!(reader.read_bits(5), Some(0b10111));
+        assert_eq!(reader.read_bits(5), Some(0b11110));
+        assert_eq!(reader.read_bits(0), Some(0));
+        assert_eq!(reader.position(), 16);
+        assert_eq!(reader.read_bits(9), None);
+        assert_eq!(reader.read_bits(8), Some(0x28));
+        assert!(reader.is_empty());
+
+        let bytes = [0x12, 0x34, 0x56, 0x78, 0x9A, 0xBC, 0xDE, 0xF0, 0x12];
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(reader.read_bits(4), Some(1));
+        assert_eq!(reader.read_bits(64), Some(0x23456789ABCDEF01));
+        assert_eq!(reader.remaining(), 4);
+    }
+
+    #[test]
+    fn sub_readers_stop_early() {
+        let mut reader = BitReader::new(&[0b1010_1100, 0xff]);
+        reader.read_bits(2);
+        let mut sub = reader.sub_reader(5).unwrap();
+        assert_eq!(reader.position(), 7);
+        assert_eq!((sub.position(), sub.remaining(), sub.end()), (2, 5, 7));
+        assert_eq!(sub.read_bits(6), None);
+        assert_eq!(sub.read_bits(5), Some(0b10110));
+        assert!(sub.is_empty());
+        assert!(sub.sub_reader(1).is_none());
+        assert!(reader.sub_reader(10).is_none());
+        assert_eq!(reader.read_bits(9), Some(0b0_1111_1111));
+    }
+}
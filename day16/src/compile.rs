@@ -0,0 +1,311 @@
+//! Compiling arithmetic like `(1 + 2) * max(3, 4, 5) > 7` into packets, for
+//! writing test transmissions by hand.
+//!
+//! `+` and `*` become sum and product packets, and `>`, `<` and `==` compare
+//! two values. Functions take the names [expression] prints: `sum`, `prod`,
+//! `min` and `max` take any number of arguments, `gt`, `lt` and `eq` exactly
+//! two. `*` binds tighter than `+`, which binds tighter than a comparison, and
+//! a comparison of more than two values needs parentheses to say which
+//! comparison goes first. Parentheses and calls nest at most [MAX_DEPTH]
+//! deep. Every packet has version 0.
+//!
+//! [expression]: crate::disasm::expression
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use common::parse::{ParseError, Span};
+use common::AocError;
+
+use crate::disasm::function;
+use crate::{Content, LengthType, Packet, MAX_DEPTH};
+
+/// Type IDs of the operators, in the order [function] names them
+const OPERATORS: [u8; 7] = [0, 1, 2, 3, 5, 6, 7];
+
+/// Comparison operators and their type IDs, longest first so `==` isn't
+/// taken for something shorter
+const COMPARISONS: [(&str, u8); 3] = [("==", 7), ("<", 6), (">", 5)];
+
+fn operator(type_id: u8, packets: Vec<Packet>) -> Packet {
+    Packet::new(0, Content::Operator(type_id, packets))
+}
+
+/// Parses an expression from the front of `rest`, one rule per precedence
+/// level
+struct Parser<'a> {
+    rest: Span<'a>,
+    /// How many parentheses and calls the parser is inside
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        let text = self.rest.as_str();
+        let spaces = text.len() - text.trim_start().len();
+        self.rest = self.rest.get(spaces..).expect("whitespace is whole chars");
+    }
+
+    /// Move past `token` if it comes next
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        match self.rest.strip_prefix(token) {
+            Some(rest) => {
+                self.rest = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), ParseError> {
+        match self.eat(token) {
+            true => Ok(()),
+            false => Err(self.rest.error(format!("expected `{token}`"))),
+        }
+    }
+
+    /// The chars up to the first that isn't `accept`, which may be none
+    fn take_while(&mut self, accept: impl Fn(char) -> bool) -> Span<'a> {
+        self.skip_whitespace();
+        let text = self.rest.as_str();
+        let end = text.find(|c| !accept(c)).unwrap_or(text.len());
+        let token = self.rest.get(..end).expect("the end is a char boundary");
+        self.rest = self.rest.get(end..).expect("the end is a char boundary");
+        token
+    }
+
+    /// The type ID of the comparison operator that comes next, if any
+    fn comparison_operator(&mut self) -> Option<u8> {
+        COMPARISONS
+            .iter()
+            .find(|(token, _)| self.eat(token))
+            .map(|&(_, type_id)| type_id)
+    }
+
+    /// A sum, or two compared
+    fn comparison(&mut self) -> Result<Packet, ParseError> {
+        let lhs = self.sum()?;
+        let Some(type_id) = self.comparison_operator() else {
+            return Ok(lhs);
+        };
+        let rhs = self.sum()?;
+
+        let operator_at = self.rest;
+        if self.comparison_operator().is_some() {
+            return Err(operator_at.trim().error(
+                "expected a comparison of two values, add parentheses to compare \
+                 the result of another",
+            ));
+        }
+        Ok(operator(type_id, vec![lhs, rhs]))
+    }
+
+    /// Products added together
+    fn sum(&mut self) -> Result<Packet, ParseError> {
+        let mut terms = vec![self.product()?];
+        while self.eat("+") {
+            terms.push(self.product()?);
+        }
+        Ok(match terms.len() {
+            1 => terms.pop().expect("there is a term"),
+            _ => operator(0, terms),
+        })
+    }
+
+    /// Values multiplied together
+    fn product(&mut self) -> Result<Packet, ParseError> {
+        let mut factors = vec![self.value()?];
+        while self.eat("*") {
+            factors.push(self.value()?);
+        }
+        Ok(match factors.len() {
+            1 => factors.pop().expect("there is a factor"),
+            _ => operator(1, factors),
+        })
+    }
+
+    /// Go a level deeper into parentheses or a call, as long as there's no
+    /// more than [MAX_DEPTH] of them
+    fn enter(&mut self, at: Span) -> Result<(), ParseError> {
+        self.depth += 1;
+        match self.depth > MAX_DEPTH {
+            true => Err(at.error(format!(
+                "expected parentheses and calls nested at most {MAX_DEPTH} deep"
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    /// A number, a function call or an expression in parentheses
+    fn value(&mut self) -> Result<Packet, ParseError> {
+        self.skip_whitespace();
+        let start = self.rest;
+        if self.eat("(") {
+            self.enter(start)?;
+            let packet = self.comparison()?;
+            self.expect(")")?;
+            self.depth -= 1;
+            return Ok(packet);
+        }
+
+        let number = self.take_while(|c| c.is_ascii_digit());
+        if !number.is_empty() {
+            return Ok(Packet::new(0, Content::Literal(number.parse()?)));
+        }
+
+        let name = self.take_while(|c| c.is_ascii_alphanumeric() || c == '_');
+        if name.is_empty() {
+            return Err(self.rest.error("expected a number, a function or `(`"));
+        }
+        let type_id = OPERATORS
+            .into_iter()
+            .find(|&type_id| function(type_id) == Some(name.as_str()))
+            .ok_or_else(|| {
+                name.error("expected a function, one of sum, prod, min, max, gt, lt or eq")
+            })?;
+
+        self.expect("(")?;
+        self.enter(name)?;
+        let mut arguments = vec![self.comparison()?];
+        while self.eat(",") {
+            arguments.push(self.comparison()?);
+        }
+        self.expect(")")?;
+        self.depth -= 1;
+
+        if type_id >= 5 && arguments.len() != 2 {
+            return Err(name.error(format!(
+                "expected {name} to compare 2 values, not {}",
+                arguments.len()
+            )));
+        }
+        Ok(operator(type_id, arguments))
+    }
+}
+
+/// The packet an expression like `sum(min(1, 2), eq(3, prod(4, 5)))`
+/// compiles to, see the module docs
+pub fn compile(source: &str) -> Result<Packet, AocError> {
+    let mut parser = Parser {
+        rest: Span::new(source),
+        depth: 0,
+    };
+    let packet = parser.comparison()?;
+    parser.skip_whitespace();
+    if !parser.rest.is_empty() {
+        return Err(parser
+            .rest
+            .error("expected `+`, `*`, `<`, `>` or `==` between values")
+            .into());
+    }
+    Ok(packet)
+}
+
+/// [compile] an expression into a line of hex like `C200B40A82`. Operators
+/// give the length of their subpackets in bits wherever it fits, and count
+/// them otherwise.
+pub fn compile_hex(source: &str) -> Result<String, AocError> {
+    compile(source)?.to_hex(|_, bits| match bits < 1 << 15 {
+        true => LengthType::Bits,
+        false => LengthType::Count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::disasm::expression;
+
+    fn error(source: &str) -> (usize, String) {
+        match compile(source) {
+            Err(AocError::Parse { error, .. }) => (error.column, error.message),
+            other => panic!("{source} compiled to {other:?}"),
+        }
+    }
+
+    #[test]
+    fn precedence() {
+        let packet = compile("(1 + 2) * max(3, 4, 5) > 7").unwrap();
+        assert_eq!(expression(&packet), "gt(prod(sum(1, 2), max(3, 4, 5)), 7)");
+        assert_eq!(packet.execute().unwrap(), 1);
+
+        let packet = compile(" 1+2*3+4 == 11 ").unwrap();
+        assert_eq!(expression(&packet), "eq(sum(1, prod(2, 3), 4), 11)");
+        assert_eq!(packet.execute().unwrap(), 1);
+
+        let packet = compile("(1 < 2) + ((3))").unwrap();
+        assert_eq!(expression(&packet), "sum(lt(1, 2), 3)");
+        assert_eq!(
+            compile("2021").unwrap(),
+            Packet::new(0, Content::Literal(2021))
+        );
+    }
+
+    #[test]
+    fn round_trips() {
+        // Part 2's examples, with their values
+        let examples = [
+            ("C200B40A82", 3),
+            ("04005AC33890", 54),
+            ("880086C3E88112", 7),
+            ("CE00C43D881120", 9),
+            ("D8005AC2A8F0", 1),
+            ("F600BC2D8F", 0),
+            ("9C005AC2F8F0", 0),
+            ("9C0141080250320F1802104A08", 1),
+        ];
+        for (hex, value) in examples {
+            let source = expression(&Packet::from_hex(hex).unwrap());
+            let packet = compile(&source).unwrap();
+            assert_eq!(expression(&packet), source);
+            assert_eq!(packet.execute().unwrap(), value, "{source}");
+
+            let (decoded, _) =
+                Packet::from_bin(&packet.to_bin(|_, _| LengthType::Bits).unwrap()).unwrap();
+            assert_eq!(decoded, packet);
+        }
+
+        let hex = compile_hex("sum(1, 2)").unwrap();
+        assert_eq!(expression(&Packet::from_hex(&hex).unwrap()), "sum(1, 2)");
+        assert_eq!(compile_hex("min(15)").unwrap(), "08002C4780");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            error("1 < 2 < 3"),
+            (
+                7,
+                "expected a comparison of two values, add parentheses to compare \
+                 the result of another"
+                    .into()
+            )
+        );
+        assert_eq!(error("(1 == 2 == 3)").0, 9);
+        assert_eq!(
+            error("1 + eq(1, 2, 3)"),
+            (5, "expected eq to compare 2 values, not 3".into())
+        );
+        assert_eq!(error("gt(1)").0, 1);
+        assert_eq!(error("1 - 2").0, 3);
+        assert_eq!(error("(1 + 2").0, 7);
+        assert_eq!(error("sum()").0, 5);
+        assert_eq!(error("avg(1, 2)").0, 1);
+        assert_eq!(error("max 1").0, 5);
+        assert_eq!(error("18446744073709551616").0, 1);
+        assert_eq!(error("").0, 1);
+
+        let nested = |depth| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        assert!(compile(&nested(MAX_DEPTH)).is_ok());
+        assert_eq!(
+            error(&nested(MAX_DEPTH + 1)),
+            (
+                MAX_DEPTH + 1,
+                format!("expected parentheses and calls nested at most {MAX_DEPTH} deep")
+            )
+        );
+        assert_eq!(error(&"max(".repeat(100_000)).0, 4 * MAX_DEPTH + 1);
+    }
+}
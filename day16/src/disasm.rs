@@ -0,0 +1,179 @@
+//! Listing a transmission packet by packet, for when an answer comes out
+//! wrong and the bits need checking by hand.
+//!
+//! Each packet gets a line with its bit offset, version, type, and either
+//! its length type and length field or its value and literal groups,
+//! indented under the operator holding it. After the packets comes the
+//! expression they add up to:
+//!
+//! ```text
+//!  0  v1 less than (type 6), length type 0: 27 bits
+//! 22    v6 literal (type 4) 10, groups 01010
+//! 33    v2 literal (type 4) 20, groups 10001 00100
+//! 49  padding, 7 bits
+//!
+//! lt(10, 20)
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use common::AocError;
+use core::fmt::Write;
+
+use crate::{decode_input, parse_hex, BitReader, Content, DecodeError, Packet};
+
+/// The packet as a function call, like `sum(min(1, 2), eq(3, prod(4, 5)))`
+pub fn expression(packet: &Packet) -> String {
+    packet.fold(|packet, arguments: Vec<String>| match packet.op {
+        Content::Literal(value) => format!("{value}"),
+        Content::Operator(type_id, _) => {
+            let function = function(type_id).unwrap_or("unknown");
+            format!("{function}({})", arguments.join(", "))
+        }
+    })
+}
+
+/// The function an operator's type ID is written as in an expression
+pub(crate) fn function(type_id: u8) -> Option<&'static str> {
+    match type_id {
+        0 => Some("sum"),
+        1 => Some("prod"),
+        2 => Some("min"),
+        3 => Some("max"),
+        5 => Some("gt"),
+        6 => Some("lt"),
+        7 => Some("eq"),
+        _ => None,
+    }
+}
+
+/// Disassemble a transmission like `38006F45291200`, see the module docs
+pub fn disassemble(hex: &str) -> Result<String, DecodeError> {
+    let hex = hex.trim();
+    let packet = Packet::from_hex(hex)?;
+    let bytes = parse_hex(hex)?;
+    let mut reader = BitReader::new(&bytes)
+        .sub_reader(hex.len() * 4)
+        .expect("the bytes hold every digit");
+
+    let mut listing = String::new();
+    let width = format!("{}", reader.remaining()).len();
+    list(&packet, &mut reader, width, &mut listing);
+    if !reader.is_empty() {
+        let position = reader.position();
+        let padding = reader.remaining();
+        writeln!(listing, "{position:>width$}  padding, {padding} bits").unwrap();
+    }
+    writeln!(listing, "\n{}", expression(&packet)).unwrap();
+    Ok(listing)
+}
+
+/// [disassemble] the transmission of a puzzle input, with errors pointing
+/// at the hex digit that went wrong like solving it would
+pub fn disassemble_input(input: &str) -> Result<String, AocError> {
+    let (hex, _) = decode_input(input)?;
+    Ok(disassemble(hex).expect("the transmission decoded already"))
+}
+
+/// Add a line for `packet` and each of its subpackets to `listing`, reading
+/// the bits the packet was decoded from alongside it
+fn list(packet: &Packet, reader: &mut BitReader, width: usize, listing: &mut String) {
+    // Packets still to list, in the order their bits come, with how deep
+    // each is nested
+    let mut stack: Vec<(&Packet, usize)> = vec![(packet, 0)];
+    while let Some((packet, depth)) = stack.pop() {
+        let start = reader.position();
+        let mut read = |bits| reader.read_bits(bits).expect("the packet decoded already");
+        let indent = "  ".repeat(depth);
+        let (version, type_id) = (read(3), read(3));
+        let name = packet.op.name();
+        write!(
+            listing,
+            "{start:>width$}  {indent}v{version} {name} (type {type_id})"
+        )
+        .unwrap();
+        match &packet.op {
+            Content::Literal(value) => {
+                let mut groups: Vec<String> = Vec::new();
+                loop {
+                    let group = read(5);
+                    groups.push(format!("{group:05b}"));
+                    if group >> 4 == 0 {
+                        break;
+                    }
+                }
+                writeln!(listing, " {value}, groups {}", groups.join(" ")).unwrap();
+            }
+            Content::Operator(_, packets) => {
+                match read(1) {
+                    0 => writeln!(listing, ", length type 0: {} bits", read(15)),
+                    _ => match read(11) {
+                        1 => writeln!(listing, ", length type 1: 1 subpacket"),
+                        count => writeln!(listing, ", length type 1: {count} subpackets"),
+                    },
+                }
+                .unwrap();
+                stack.extend(packets.iter().rev().map(|packet| (packet, depth + 1)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listing() {
+        assert_eq!(
+            disassemble("38006F45291200").unwrap(),
+            " 0  v1 less than (type 6), length type 0: 27 bits\n\
+             22    v6 literal (type 4) 10, groups 01010\n\
+             33    v2 literal (type 4) 20, groups 10001 00100\n\
+             49  padding, 7 bits\n\
+             \n\
+             lt(10, 20)\n"
+        );
+        assert_eq!(
+            disassemble("8A004A801A8002F478").unwrap(),
+            " 0  v4 minimum (type 2), length type 1: 1 subpacket\n\
+             18    v1 minimum (type 2), length type 1: 1 subpacket\n\
+             36      v5 minimum (type 2), length type 0: 11 bits\n\
+             58        v6 literal (type 4) 15, groups 01111\n\
+             69  padding, 3 bits\n\
+             \n\
+             min(min(min(15)))\n"
+        );
+        assert_eq!(
+            disassemble("D2FE28").unwrap(),
+            " 0  v6 literal (type 4) 2021, groups 10111 11110 00101\n\
+             21  padding, 3 bits\n\
+             \n\
+             2021\n"
+        );
+        assert!(disassemble("D2FE").is_err());
+        assert!(disassemble_input("D2FE\n").is_err());
+    }
+
+    #[test]
+    fn expressions() {
+        let literal = |value| Packet::new(0, Content::Literal(value));
+        let operator = |type_id, packets| Packet::new(0, Content::Operator(type_id, packets));
+        let packet = operator(
+            0,
+            vec![
+                operator(2, vec![literal(1), literal(2)]),
+                operator(
+                    7,
+                    vec![literal(3), operator(1, vec![literal(4), literal(5)])],
+                ),
+            ],
+        );
+        assert_eq!(expression(&packet), "sum(min(1, 2), eq(3, prod(4, 5)))");
+
+        let packet = Packet::from_hex("9C0141080250320F1802104A08").unwrap();
+        assert_eq!(expression(&packet), "eq(sum(1, 3), prod(2, 2))");
+    }
+}
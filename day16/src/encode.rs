@@ -0,0 +1,311 @@
+//! Encoding packets back into BITS transmissions, the reverse of
+//! [Packet::from_bin] and [Packet::from_hex].
+//!
+//! Literals are split into as few 5 bit groups as hold their value. An
+//! operator can say where its subpackets end either way, so the caller picks
+//! a [LengthType] for each one.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use common::AocError;
+use core::fmt::Write;
+
+use crate::{Content, Packet};
+
+/// Bits in a packet's total subpacket length
+const LENGTH_BITS: usize = 15;
+
+/// Bits in a packet's subpacket count
+const COUNT_BITS: usize = 11;
+
+/// How an operator packet says where its subpackets end
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthType {
+    /// Length type 0, the total length of the subpackets in 15 bits
+    Bits,
+    /// Length type 1, the number of subpackets in 11 bits
+    Count,
+}
+
+impl LengthType {
+    /// The length type ID in the packet header
+    pub fn id(&self) -> u8 {
+        match self {
+            LengthType::Bits => 0,
+            LengthType::Count => 1,
+        }
+    }
+
+    /// Bits in the length field that follows the ID
+    fn width(&self) -> usize {
+        match self {
+            LengthType::Bits => LENGTH_BITS,
+            LengthType::Count => COUNT_BITS,
+        }
+    }
+}
+
+/// Bits packed into bytes most significant first, the way [BitReader]
+/// reads them, with the last byte padded with `0`s
+///
+/// [BitReader]: crate::BitReader
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    /// Append `value` as `width` bits
+    fn push(&mut self, value: u64, width: usize) {
+        for bit in (0..width).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= (((value >> bit) & 1) as u8) << (7 - self.len % 8);
+            self.len += 1;
+        }
+    }
+}
+
+/// Groups of 4 bits a literal's value is split into
+fn literal_groups(value: u64) -> u32 {
+    (u64::BITS - value.leading_zeros()).div_ceil(4).max(1)
+}
+
+impl Packet {
+    /// Encode the packet as a string of `0`s and `1`s, without any padding.
+    ///
+    /// `length_type` picks how each operator gives the extent of its
+    /// subpackets, given the operator and how many bits its subpackets take
+    /// up. It's called on the subpackets before the operator holding them.
+    ///
+    /// Fails when a version or type ID doesn't fit in its 3 bits, type 4 has
+    /// subpackets, or the subpackets don't fit the length type picked.
+    pub fn to_bin(
+        &self,
+        length_type: impl FnMut(&Packet, usize) -> LengthType,
+    ) -> Result<String, AocError> {
+        let bits = self.encode(length_type)?;
+        Ok((0..bits.len)
+            .map(|i| match bits.bytes[i / 8] >> (7 - i % 8) & 1 {
+                0 => '0',
+                _ => '1',
+            })
+            .collect())
+    }
+
+    /// Encode the packet as bytes, padded with `0`s to the end of the last
+    /// one, see [Packet::to_bin]
+    pub fn to_bytes(
+        &self,
+        length_type: impl FnMut(&Packet, usize) -> LengthType,
+    ) -> Result<Vec<u8>, AocError> {
+        Ok(self.encode(length_type)?.bytes)
+    }
+
+    /// Encode the packet as a line of uppercase hex like `D2FE28`, see
+    /// [Packet::to_bytes]
+    pub fn to_hex(
+        &self,
+        length_type: impl FnMut(&Packet, usize) -> LengthType,
+    ) -> Result<String, AocError> {
+        let mut hex = String::new();
+        for byte in self.to_bytes(length_type)? {
+            write!(hex, "{byte:02X}").expect("writing to a String can't fail");
+        }
+        Ok(hex)
+    }
+
+    /// The packet's bits. The length field of an operator comes before its
+    /// subpackets but depends on their size, so the sizes and length types
+    /// are all worked out first.
+    fn encode(
+        &self,
+        mut length_type: impl FnMut(&Packet, usize) -> LengthType,
+    ) -> Result<BitWriter, AocError> {
+        let mut fields = Vec::new();
+        self.plan(&mut fields, &mut length_type)?;
+        let mut bits = BitWriter::default();
+        self.write(&mut bits, &mut fields.into_iter());
+        Ok(bits)
+    }
+
+    /// Check the packet can be encoded, add the length type and field of
+    /// each operator to `fields` in the order they're written, and return
+    /// how many bits the packet takes
+    fn plan(
+        &self,
+        fields: &mut Vec<(LengthType, usize)>,
+        length_type: &mut dyn FnMut(&Packet, usize) -> LengthType,
+    ) -> Result<usize, AocError> {
+        if self.version > 7 {
+            return Err(AocError::Semantic(format!(
+                "version {} doesn't fit in 3 bits",
+                self.version
+            )));
+        }
+        match &self.op {
+            Content::Literal(value) => Ok(6 + 5 * literal_groups(*value) as usize),
+            Content::Operator(type_id @ (4 | 8..), _) => Err(AocError::Semantic(format!(
+                "operators can't have type ID {type_id}"
+            ))),
+            Content::Operator(_, packets) => {
+                // Filled in once the subpackets are sized
+                let slot = fields.len();
+                fields.push((LengthType::Bits, 0));
+                let mut subpackets = 0;
+                for packet in packets {
+                    subpackets += packet.plan(fields, length_type)?;
+                }
+                let length_type = length_type(self, subpackets);
+                let field = match length_type {
+                    LengthType::Bits => subpackets,
+                    LengthType::Count => packets.len(),
+                };
+                let width = length_type.width();
+                if field >> width != 0 {
+                    return Err(AocError::Semantic(format!(
+                        "{} packet's subpackets don't fit length type {}, \
+                         {field} is more than {width} bits",
+                        self.op.name(),
+                        length_type.id()
+                    )));
+                }
+                fields[slot] = (length_type, field);
+                Ok(7 + width + subpackets)
+            }
+        }
+    }
+
+    /// Append the bits of a packet that's been through [Packet::plan]
+    fn write(&self, bits: &mut BitWriter, fields: &mut impl Iterator<Item = (LengthType, usize)>) {
+        bits.push(self.version as u64, 3);
+        match &self.op {
+            Content::Literal(value) => {
+                bits.push(4, 3);
+                for group in (0..literal_groups(*value)).rev() {
+                    bits.push((group > 0) as u64, 1);
+                    bits.push((value >> (group * 4)) & 0xf, 4);
+                }
+            }
+            Content::Operator(type_id, packets) => {
+                let (length_type, field) = fields.next().expect("every operator was planned");
+                bits.push(*type_id as u64, 3);
+                bits.push(length_type.id() as u64, 1);
+                bits.push(field as u64, length_type.width());
+                for packet in packets {
+                    packet.write(bits, fields);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// Every example transmission from the puzzle
+    const EXAMPLES: [&str; 15] = [
+        "D2FE28",
+        "38006F45291200",
+        "EE00D40C823060",
+        "8A004A801A8002F478",
+        "620080001611562C8802118E34",
+        "C0015000016115A2E0802F182340",
+        "A0016C880162017C3686B18A3D4780",
+        "C200B40A82",
+        "04005AC33890",
+        "880086C3E88112",
+        "CE00C43D881120",
+        "D8005AC2A8F0",
+        "F600BC2D8F",
+        "9C005AC2F8F0",
+        "9C0141080250320F1802104A08",
+    ];
+
+    /// Operators in the packet and everything inside it
+    fn operators(packet: &Packet) -> usize {
+        match &packet.op {
+            Content::Literal(_) => 0,
+            Content::Operator(_, packets) => 1 + packets.iter().map(operators).sum::<usize>(),
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        for hex in EXAMPLES {
+            let packet = Packet::from_hex(hex).unwrap();
+            for length_type in [LengthType::Bits, LengthType::Count] {
+                let bits = packet.to_bin(|_, _| length_type).unwrap();
+                let (decoded, end) = Packet::from_bin(&bits).unwrap();
+                assert_eq!(decoded, packet, "{hex} with {length_type:?}");
+                assert_eq!(end, bits.len());
+            }
+
+            // The transmission itself comes back for the length types it
+            // was sent with, whichever those are
+            let operators = operators(&packet);
+            let found = (0..1 << operators).any(|choices: u32| {
+                let mut operator = 0;
+                let encoded = packet.to_hex(|_, _| {
+                    operator += 1;
+                    match choices >> (operator - 1) & 1 {
+                        0 => LengthType::Bits,
+                        _ => LengthType::Count,
+                    }
+                });
+                encoded.unwrap() == hex
+            });
+            assert!(found, "no length types give back {hex}");
+        }
+    }
+
+    #[test]
+    fn literal_groups() {
+        let literal = |value| Packet::new(6, Content::Literal(value));
+        assert_eq!(
+            literal(2021).to_hex(|_, _| LengthType::Bits).unwrap(),
+            "D2FE28"
+        );
+        assert_eq!(
+            literal(2021).to_bytes(|_, _| LengthType::Bits).unwrap(),
+            [0xD2, 0xFE, 0x28]
+        );
+        assert_eq!(
+            literal(0).to_bin(|_, _| LengthType::Bits).unwrap(),
+            "11010000000"
+        );
+        assert_eq!(
+            literal(16).to_bin(|_, _| LengthType::Bits).unwrap(),
+            "1101001000100000"
+        );
+        let bits = literal(u64::MAX).to_bin(|_, _| LengthType::Bits).unwrap();
+        assert_eq!(bits.len(), 6 + 16 * 5);
+        assert_eq!(Packet::from_bin(&bits).unwrap().0, literal(u64::MAX));
+    }
+
+    #[test]
+    fn unencodable_packets() {
+        let literal = Packet::new(0, Content::Literal(1));
+        assert!(Packet::new(8, Content::Literal(1))
+            .to_bin(|_, _| LengthType::Bits)
+            .is_err());
+        let four = Packet::new(0, Content::Operator(4, vec![]));
+        assert!(four.to_bin(|_, _| LengthType::Bits).is_err());
+
+        // 2048 subpackets are too many to count in 11 bits, but their 22528
+        // bits fit in a 15 bit length
+        let many = Packet::new(
+            0,
+            Content::Operator(0, (0..2048).map(|_| literal.clone()).collect()),
+        );
+        assert!(many.to_bin(|_, _| LengthType::Count).is_err());
+        let bits = many.to_bin(|_, _| LengthType::Bits).unwrap();
+        assert_eq!(Packet::from_bin(&bits).unwrap().0, many);
+    }
+}
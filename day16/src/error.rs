@@ -0,0 +1,100 @@
+//! What can go wrong decoding a transmission.
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::MAX_DEPTH;
+
+/// Where and why a transmission couldn't be decoded
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodeError {
+    /// Offset of the bit where decoding went wrong, from the start of the
+    /// transmission
+    pub bit: usize,
+    /// The packet it went wrong in, as the index of each subpacket on the
+    /// way down from the outermost packet. Empty for the outermost packet.
+    pub path: Vec<usize>,
+    pub kind: ErrorKind,
+}
+
+/// What was expected instead
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The transmission ended before a field, like `"a literal group"`
+    Truncated(&'static str),
+    /// An operator's subpackets take `length` bits, but its parent only has
+    /// `available` bits left
+    Overrun { length: usize, available: usize },
+    /// A field, like `"a literal group"`, runs past the end of the `length`
+    /// bits of subpackets that an operator holds
+    PastLength(&'static str, usize),
+    /// A literal group that doesn't fit, the value is already using some of
+    /// its top 4 bits of 64 so shifting in another group would lose them
+    LiteralTooBig,
+    /// A packet nested more than [MAX_DEPTH] deep
+    TooDeep,
+    /// A character that isn't a hex digit
+    InvalidHex(char),
+    /// A character that isn't a `0` or `1`
+    InvalidBit(char),
+    /// Padding after the outermost packet that isn't all `0`s
+    NonZeroPadding,
+}
+
+impl DecodeError {
+    pub fn new(bit: usize, kind: ErrorKind) -> Self {
+        DecodeError {
+            bit,
+            path: Vec::new(),
+            kind,
+        }
+    }
+
+    /// The error as seen from the operator holding the packet it came from,
+    /// subpacket `index` of the operator
+    pub(crate) fn within(mut self, index: usize) -> Self {
+        self.path.insert(0, index);
+        self
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Truncated(field) => write!(f, "expected {field}, transmission ended"),
+            ErrorKind::Overrun { length, available } => write!(
+                f,
+                "expected a subpacket length of at most {available} bits, not {length}"
+            ),
+            ErrorKind::PastLength(field, length) => write!(
+                f,
+                "expected {field} within the {length} bits of subpackets, they ended"
+            ),
+            ErrorKind::LiteralTooBig => write!(f, "expected the literal to fit in 64 bits"),
+            ErrorKind::TooDeep => {
+                write!(f, "expected packets nested at most {MAX_DEPTH} deep")
+            }
+            ErrorKind::InvalidHex(found) => write!(f, "expected a hex digit, found {found:?}"),
+            ErrorKind::InvalidBit(found) => write!(f, "expected a bit, found {found:?}"),
+            ErrorKind::NonZeroPadding => write!(f, "expected 0s padding the transmission"),
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bit {} in ", self.bit)?;
+        match self.path.split_first() {
+            None => write!(f, "the outermost packet")?,
+            Some((first, rest)) => {
+                write!(f, "subpacket {first}")?;
+                for index in rest {
+                    write!(f, ".{index}")?;
+                }
+            }
+        }
+        write!(f, ": {}", self.kind)
+    }
+}
+
+impl core::error::Error for DecodeError {}
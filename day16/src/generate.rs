@@ -0,0 +1,83 @@
+//! Random BITS transmissions for `aoc gen`.
+
+use crate::{Content, LengthType, Packet};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use common::random::Rng;
+
+/// Packets in the real input
+pub const SIZE: usize = 250;
+
+/// Values stay below this, so no sum or product overflows
+const MAX_VALUE: u64 = 1 << 48;
+
+/// Operators take at most this many subpackets, unless they compare two
+const MAX_SUBPACKETS: usize = 8;
+
+/// A random packet made of `size` packets in all, along with its value
+fn packet(size: usize, rng: &mut Rng) -> (Packet, u64) {
+    let version = rng.range(0..=7);
+    if size <= 1 {
+        let bits: u32 = rng.range(1..=36);
+        let value = rng.below(1 << bits);
+        return (Packet::new(version, Content::Literal(value)), value);
+    }
+
+    let subpackets = size - 1;
+    let type_id = match subpackets {
+        1 => *rng.choose(&[0, 1, 2, 3]).expect("there are types"),
+        _ => *rng.choose(&[0, 1, 2, 3, 5, 6, 7]).expect("there are types"),
+    };
+    let count = match type_id {
+        5..=7 => 2,
+        _ => rng.range(1..=subpackets.min(MAX_SUBPACKETS)),
+    };
+    // Share out the packets between the subpackets, at least one each
+    let mut sizes: Vec<usize> = vec![1; count];
+    for _ in count..subpackets {
+        sizes[rng.below(count as u64) as usize] += 1;
+    }
+
+    let (packets, values): (Vec<Packet>, Vec<u64>) =
+        sizes.into_iter().map(|size| packet(size, rng)).unzip();
+    let value = match type_id {
+        0 => values.iter().try_fold(0u64, |acc, &x| acc.checked_add(x)),
+        1 => values.iter().try_fold(1u64, |acc, &x| acc.checked_mul(x)),
+        2 => values.iter().copied().min(),
+        5 => Some((values[0] > values[1]) as u64),
+        6 => Some((values[0] < values[1]) as u64),
+        7 => Some((values[0] == values[1]) as u64),
+        _ => values.iter().copied().max(),
+    };
+    match value {
+        Some(value) if value < MAX_VALUE => (
+            Packet::new(version, Content::Operator(type_id, packets)),
+            value,
+        ),
+        // Too big, take the maximum instead, which is no bigger than the
+        // subpackets
+        _ => {
+            let value = values.iter().copied().max().unwrap_or(0);
+            (Packet::new(version, Content::Operator(3, packets)), value)
+        }
+    }
+}
+
+/// One line of hex for a transmission of `size` packets, padded with zeros
+/// to a whole number of bytes like the real input. There are at least two,
+/// since part 2 needs an operator on the outside.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let (packet, _) = packet(size.max(2), rng);
+    let mut hex = packet
+        .to_hex(|_, bits| {
+            if bits < 1 << 15 && rng.chance(1, 2) {
+                LengthType::Bits
+            } else {
+                LengthType::Count
+            }
+        })
+        .expect("generated packets can always be encoded");
+    hex.push('\n');
+    hex
+}
@@ -0,0 +1,878 @@
+//! Day 16: Packet Decoder. Decoding and evaluating BITS transmissions.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod bits;
+pub mod compile;
+pub mod disasm;
+pub mod encode;
+mod error;
+pub mod generate;
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use common::outline::Outline;
+use common::parse::Span;
+use common::random::Rng;
+use common::{debug, trace, Answer, AocError, Part, Solution};
+
+pub use bits::BitReader;
+pub use encode::LengthType;
+pub use error::{DecodeError, ErrorKind};
+
+/// How many packets deep a transmission can nest, counting the outermost.
+/// Decoding anything deeper fails rather than running out of stack.
+pub const MAX_DEPTH: usize = 256;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Packet data type
+pub struct Packet {
+    version: u8,
+    op: Content,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+/// Union-y use of struct-like-enum to hold the content of the [Packet].
+pub enum Content {
+    /// A number, packet type 4
+    Literal(u64),
+    /// Any other type ID and its subpackets
+    Operator(u8, Vec<Packet>),
+}
+
+impl Content {
+    /// What the packet is, like `literal` or `sum`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Content::Literal(_) => "literal",
+            Content::Operator(0, _) => "sum",
+            Content::Operator(1, _) => "product",
+            Content::Operator(2, _) => "minimum",
+            Content::Operator(3, _) => "maximum",
+            Content::Operator(5, _) => "greater than",
+            Content::Operator(6, _) => "less than",
+            Content::Operator(7, _) => "equal to",
+            Content::Operator(_, _) => "unknown",
+        }
+    }
+
+    /// Decode the content of a packet, the bits after its version
+    ///
+    /// Reads Packet type ID and processes accordingly, `depth` is how deep the
+    /// packet is nested
+    fn process(reader: &mut BitReader, depth: usize) -> Result<Self, DecodeError> {
+        match read_field(reader, 3, "a type ID")? {
+            4 => {
+                trace!("found literal at bit {}", reader.position());
+                Ok(Content::Literal(Content::process_literal(reader)?))
+            }
+            x => {
+                trace!("found operator at bit {}", reader.position());
+                Ok(Content::Operator(
+                    x as u8,
+                    Content::process_operator(reader, depth)?,
+                ))
+            }
+        }
+    }
+
+    fn process_literal(reader: &mut BitReader) -> Result<u64, DecodeError> {
+        let start = reader.position();
+        let mut value: u64 = 0;
+        loop {
+            let group_start = reader.position();
+            let group = read_field(reader, 5, "a literal group")?;
+            trace!("literal group {group:05b}");
+            if value >> 60 != 0 {
+                return Err(DecodeError::new(group_start, ErrorKind::LiteralTooBig));
+            }
+            value = (value << 4) | (group & 0xf);
+
+            // Last number is when "header" bit is low
+            if group >> 4 == 0 {
+                break;
+            }
+        }
+
+        trace!("literal took {} bits", reader.position() - start);
+        Ok(value)
+    }
+
+    fn process_operator(reader: &mut BitReader, depth: usize) -> Result<Vec<Packet>, DecodeError> {
+        let mut packets: Vec<Packet> = Vec::new();
+        match read_field(reader, 1, "a length type ID")? {
+            0 => {
+                let length = read_field(reader, 15, "a subpacket length")? as usize;
+                debug!("subpackets take {length} bits");
+                let start = reader.position();
+                let available = reader.remaining();
+                let mut subpackets = reader.sub_reader(length).ok_or_else(|| {
+                    DecodeError::new(start, ErrorKind::Overrun { length, available })
+                })?;
+
+                while !subpackets.is_empty() {
+                    trace!("subpacket at bit {}", subpackets.position());
+                    let packet =
+                        Packet::read_nested(&mut subpackets, depth + 1).map_err(|error| {
+                            // Running out of bits here means running out of
+                            // subpackets, the transmission goes on past them
+                            let error = error.within(packets.len());
+                            match error.kind {
+                                ErrorKind::Truncated(field) => DecodeError {
+                                    kind: ErrorKind::PastLength(field, length),
+                                    ..error
+                                },
+                                _ => error,
+                            }
+                        })?;
+                    packets.push(packet);
+                }
+            }
+            _ => {
+                let packet_count = read_field(reader, 11, "a subpacket count")?;
+                debug!("{packet_count} subpackets");
+
+                for index in 0..packet_count as usize {
+                    trace!("subpacket at bit {}", reader.position());
+                    packets.push(
+                        Packet::read_nested(reader, depth + 1)
+                            .map_err(|error| error.within(index))?,
+                    );
+                }
+            }
+        }
+
+        Ok(packets)
+    }
+
+    /// Sum of the versions of the subpackets and everything inside them, 0 for
+    /// a literal
+    pub fn subpackets_version_sum(&self) -> u64 {
+        let mut sum: u64 = 0;
+        if let Content::Operator(_, packets) = self {
+            for packet in packets {
+                sum += packet.version_sum();
+            }
+        }
+        sum
+    }
+}
+
+impl Packet {
+    pub fn new(version: u8, content: Content) -> Self {
+        Packet {
+            version,
+            op: content,
+        }
+    }
+
+    /// The 3 bit version number
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn content(&self) -> &Content {
+        &self.op
+    }
+
+    /// Decode a transmission like `D2FE28`. Anything after the outermost
+    /// packet is padding, which has to be `0`s.
+    pub fn from_hex(hex: &str) -> Result<Self, DecodeError> {
+        let hex = hex.trim();
+        let bytes = parse_hex(hex)?;
+        let mut reader = BitReader::new(&bytes)
+            .sub_reader(hex.len() * 4)
+            .expect("the bytes hold every digit");
+        let packet = Packet::read(&mut reader)?;
+
+        while !reader.is_empty() {
+            let start = reader.position();
+            let width = reader.remaining().min(64);
+            let padding = reader.read_bits(width).expect("there are enough bits left");
+            if padding != 0 {
+                let first_one = start + width - (u64::BITS - padding.leading_zeros()) as usize;
+                return Err(DecodeError::new(first_one, ErrorKind::NonZeroPadding));
+            }
+        }
+        Ok(packet)
+    }
+
+    /// Decode the packet at the start of a string of `0`s and `1`s, returning
+    /// it along with the number of bits it took up. Anything after the packet
+    /// is left alone.
+    pub fn from_bin(bin: &str) -> Result<(Self, usize), DecodeError> {
+        let mut bytes: Vec<u8> = vec![0; bin.len().div_ceil(8)];
+        for (i, bit) in bin.chars().enumerate() {
+            match bit {
+                '0' => (),
+                '1' => bytes[i / 8] |= 0x80 >> (i % 8),
+                _ => return Err(DecodeError::new(i, ErrorKind::InvalidBit(bit))),
+            }
+        }
+
+        let mut reader = BitReader::new(&bytes)
+            .sub_reader(bin.chars().count())
+            .expect("the bytes hold every bit");
+        let packet = Packet::read(&mut reader)?;
+        Ok((packet, reader.position()))
+    }
+
+    /// Decode the packet at the reader's position, leaving the reader just
+    /// after it
+    pub fn read(reader: &mut BitReader) -> Result<Self, DecodeError> {
+        Packet::read_nested(reader, 1)
+    }
+
+    /// [Packet::read] a packet nested `depth` deep
+    fn read_nested(reader: &mut BitReader, depth: usize) -> Result<Self, DecodeError> {
+        let start = reader.position();
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::new(start, ErrorKind::TooDeep));
+        }
+        let version = read_field(reader, 3, "a version")? as u8;
+        let op = Content::process(reader, depth)?;
+        debug!(
+            "{} packet, version {version}, {} bits",
+            op.name(),
+            reader.position() - start
+        );
+        Ok(Packet { version, op })
+    }
+
+    /// Combine the results for the subpackets of every packet into one for
+    /// the packet, from the innermost out. Walks the tree with a stack of its
+    /// own rather than recursing.
+    fn fold<T>(&self, mut combine: impl FnMut(&Packet, Vec<T>) -> T) -> T {
+        // Each packet on the way down, with the results of the subpackets
+        // done so far
+        let mut stack: Vec<(&Packet, Vec<T>)> = vec![(self, Vec::new())];
+        loop {
+            let (packet, done) = stack.last().expect("the outermost packet is left");
+            let next = match &packet.op {
+                Content::Literal(_) => None,
+                Content::Operator(_, packets) => packets.get(done.len()),
+            };
+            if let Some(next) = next {
+                stack.push((next, Vec::new()));
+                continue;
+            }
+
+            let (packet, done) = stack.pop().expect("the outermost packet is left");
+            let result = combine(packet, done);
+            match stack.last_mut() {
+                Some((_, parent)) => parent.push(result),
+                None => return result,
+            }
+        }
+    }
+
+    /// Returns the sum of this packet's version and the sum of the version of
+    /// all contained packets, if any, contained within.
+    pub fn version_sum(&self) -> u64 {
+        self.fold(|packet, sums| packet.version as u64 + sums.iter().sum::<u64>())
+    }
+
+    /// perform the computation denote by the packet's operator
+    ///
+    /// Fails when called on packet containing Content::Literal, or when an
+    /// operator doesn't have the subpackets it needs or its value doesn't fit
+    /// in 64 bits.
+    pub fn execute(&self) -> Result<u64, AocError> {
+        if let Content::Literal(_) = self.op {
+            return Err(AocError::Semantic(format!(
+                "Invalid Content to execute! got {:?}",
+                self.op
+            )));
+        }
+        self.fold(|packet, values| match packet.op {
+            Content::Literal(x) => Ok(x),
+            Content::Operator(type_id, _) => apply(
+                type_id,
+                &values.into_iter().collect::<Result<Vec<u64>, AocError>>()?,
+            ),
+        })
+    }
+}
+
+/// The value of an operator of type `type_id` with subpackets worth `values`
+fn apply(type_id: u8, values: &[u64]) -> Result<u64, AocError> {
+    match type_id {
+        // sum
+        0 => values
+            .iter()
+            .try_fold(0_u64, |sum, &x| sum.checked_add(x))
+            .ok_or_else(|| AocError::Semantic("sum packet doesn't fit in 64 bits".to_owned())),
+
+        // product
+        1 => values
+            .iter()
+            .try_fold(1_u64, |product, &x| product.checked_mul(x))
+            .ok_or_else(|| AocError::Semantic("product packet doesn't fit in 64 bits".to_owned())),
+
+        // minimum
+        2 => values
+            .iter()
+            .copied()
+            .min()
+            .ok_or_else(|| AocError::Semantic("minimum packet has no subpackets".to_owned())),
+
+        // maximum
+        3 => values
+            .iter()
+            .copied()
+            .max()
+            .ok_or_else(|| AocError::Semantic("maximum packet has no subpackets".to_owned())),
+
+        // greater than
+        5 => comparison(values).map(|(lhs, rhs)| (lhs > rhs) as u64),
+
+        // less than
+        6 => comparison(values).map(|(lhs, rhs)| (lhs < rhs) as u64),
+
+        // equal to
+        7 => comparison(values).map(|(lhs, rhs)| (lhs == rhs) as u64),
+        _ => Err(AocError::Semantic(format!(
+            "Invalid Content to execute! got type ID {type_id}"
+        ))),
+    }
+}
+
+/// The two values a comparison operator compares
+fn comparison(values: &[u64]) -> Result<(u64, u64), AocError> {
+    match *values {
+        [lhs, rhs] => Ok((lhs, rhs)),
+        _ => Err(AocError::Semantic(format!(
+            "comparison packets need exactly 2 subpackets, got {}",
+            values.len()
+        ))),
+    }
+}
+
+/// The packet and its subpackets, each labelled with its type, version and
+/// value
+pub fn packet_outline(packet: &Packet) -> Outline {
+    let (_, outline) = packet.fold(|packet, subpackets: Vec<(Option<u64>, Outline)>| {
+        let (values, children): (Vec<Option<u64>>, Vec<Outline>) = subpackets.into_iter().unzip();
+        let value = match packet.op {
+            Content::Literal(x) => Some(x),
+            Content::Operator(type_id, _) => values
+                .into_iter()
+                .collect::<Option<Vec<u64>>>()
+                .and_then(|values| apply(type_id, &values).ok()),
+        };
+        let label = format!(
+            "{} v{} = {}",
+            packet.op.name(),
+            packet.version,
+            value.map_or("?".to_owned(), |x| format!("{x}"))
+        );
+        (value, Outline::new(label, children))
+    });
+    outline
+}
+
+/// The next `width` bits of `reader`, `field` says what they are for when the
+/// transmission ends first
+fn read_field(
+    reader: &mut BitReader,
+    width: usize,
+    field: &'static str,
+) -> Result<u64, DecodeError> {
+    reader
+        .read_bits(width)
+        .ok_or_else(|| DecodeError::new(reader.position(), ErrorKind::Truncated(field)))
+}
+
+/// The bytes of a line of hex, two digits to a byte. An odd digit out takes
+/// up the top of the last byte.
+fn parse_hex(hex: &str) -> Result<Vec<u8>, DecodeError> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(hex.len().div_ceil(2));
+    for (i, digit) in hex.chars().enumerate() {
+        let nibble = digit
+            .to_digit(16)
+            .ok_or_else(|| DecodeError::new(i * 4, ErrorKind::InvalidHex(digit)))?
+            as u8;
+        match i % 2 {
+            0 => bytes.push(nibble << 4),
+            _ => *bytes.last_mut().expect("pushed the first digit") |= nibble,
+        }
+    }
+    Ok(bytes)
+}
+
+/// The transmission on the first line of a puzzle input and its outermost
+/// packet, with errors pointing at the hex digit that went wrong
+fn decode_input(input: &str) -> Result<(&str, Packet), AocError> {
+    let input = Span::new(input);
+    let transmission = match input.lines().next() {
+        Some(line) if !line.is_empty() => line,
+        _ => return Err(input.error("expected a hex transmission").into()),
+    };
+    let hex = transmission.trim();
+    match Packet::from_hex(hex.as_str()) {
+        Ok(packet) => Ok((hex.as_str(), packet)),
+        Err(error) => {
+            let digit = hex.get(error.bit / 4..).unwrap_or(hex);
+            Err(digit.error(error.to_string()).into())
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    /// The outermost packet of the transmission
+    type Input = Packet;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(decode_input(input)?.1)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(input.version_sum().into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(input.execute()?.into())
+    }
+
+    /// The packet tree, the same for both parts
+    fn outline(input: &Self::Input, _part: Part) -> Option<Outline> {
+        Some(packet_outline(input))
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+/// Tests come from the problem statement examples
+mod tests {
+    use super::*;
+
+    /// The packet at the start of a hex transmission, and the bits it took up
+    fn decode(hex: &str) -> (Packet, usize) {
+        let bytes = parse_hex(hex).unwrap();
+        let mut reader = BitReader::new(&bytes);
+        let packet = Packet::read(&mut reader).unwrap();
+        (packet, reader.position())
+    }
+
+    #[test]
+    fn hex_bytes() {
+        assert_eq!(parse_hex("D2FE28").unwrap(), [0xD2, 0xFE, 0x28]);
+        assert_eq!(
+            parse_hex("38006F45291200").unwrap(),
+            [0x38, 0x00, 0x6F, 0x45, 0x29, 0x12, 0x00]
+        );
+        assert_eq!(parse_hex("abc").unwrap(), [0xAB, 0xC0]);
+        assert_eq!(
+            parse_hex("A-").unwrap_err(),
+            DecodeError::new(4, ErrorKind::InvalidHex('-'))
+        );
+    }
+
+    #[test]
+    fn packet_from_hex() {
+        let packet = Packet::from_hex("8A004A801A8002F478\n").unwrap();
+        assert_eq!(packet.version(), 4);
+        assert_eq!(packet.version_sum(), 16);
+        assert!(
+            matches!(packet.content(), Content::Operator(2, subpackets) if subpackets.len() == 1)
+        );
+
+        assert_eq!(
+            Packet::from_hex("C2X0").unwrap_err(),
+            DecodeError::new(8, ErrorKind::InvalidHex('X'))
+        );
+    }
+
+    #[test]
+    fn decode_errors() {
+        let error = Packet::from_hex("D2FE").unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::new(16, ErrorKind::Truncated("a literal group"))
+        );
+        assert_eq!(
+            error.to_string(),
+            "bit 16 in the outermost packet: expected a literal group, transmission ended"
+        );
+
+        // The third literal is cut off
+        let error = Packet::from_hex("EE00D40C8230").unwrap_err();
+        assert_eq!((error.bit, error.path.as_slice()), (46, &[2][..]));
+        assert_eq!(error.kind, ErrorKind::Truncated("a literal group"));
+
+        // The subpackets are meant to take up 27 bits, but there are only 18
+        let error = Packet::from_hex("38006F4529").unwrap_err();
+        assert_eq!(
+            error,
+            DecodeError::new(
+                22,
+                ErrorKind::Overrun {
+                    length: 27,
+                    available: 18
+                }
+            )
+        );
+
+        // A length of 26 bits cuts off the second subpacket instead
+        let error = Packet::from_hex("38006B45291200").unwrap_err();
+        assert_eq!((error.bit, error.path.as_slice()), (44, &[1][..]));
+        assert_eq!(error.kind, ErrorKind::PastLength("a literal group", 26));
+        assert_eq!(
+            error.to_string(),
+            "bit 44 in subpacket 1: \
+             expected a literal group within the 26 bits of subpackets, they ended"
+        );
+
+        // The innermost operator's subpackets overrun the two around it
+        let error = Packet::from_hex("8A004A801A8002F4").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "bit 58 in subpacket 0.0: \
+             expected a subpacket length of at most 6 bits, not 11"
+        );
+
+        let error = Packet::from_hex("D2FE29").unwrap_err();
+        assert_eq!(error, DecodeError::new(23, ErrorKind::NonZeroPadding));
+
+        let too_big = format!("000100{}01111", "11111".repeat(16));
+        assert_eq!(
+            Packet::from_bin(&too_big).unwrap_err(),
+            DecodeError::new(86, ErrorKind::LiteralTooBig)
+        );
+        assert_eq!(
+            Packet::from_bin("01x0").unwrap_err(),
+            DecodeError::new(2, ErrorKind::InvalidBit('x'))
+        );
+        assert!(Packet::from_bin("").is_err());
+
+        // Solving points at the hex digit
+        assert!(Day16::parse("\n").is_err());
+        let error = Day16::parse("D2FE\n").unwrap_err();
+        assert!(matches!(error, AocError::Parse { ref error, .. } if error.column == 5));
+    }
+
+    #[test]
+    fn nesting() {
+        // Minimum operators holding one subpacket each, around a literal 1
+        let nested = |depth: usize| {
+            let operators = "000010100000000001".repeat(depth - 1);
+            Packet::from_bin(&format!("{operators}00010000001"))
+        };
+
+        let (packet, _) = nested(MAX_DEPTH).unwrap();
+        assert_eq!(packet.version_sum(), 0);
+        assert_eq!(packet.execute().unwrap(), 1);
+        assert_eq!(packet_outline(&packet).label, "minimum v0 = 1");
+        let hex = packet.to_hex(|_, _| LengthType::Count).unwrap();
+        let expression = disasm::expression(&packet);
+        assert_eq!(expression.matches("min(").count(), MAX_DEPTH - 1);
+        assert!(disasm::disassemble(&hex)
+            .unwrap()
+            .ends_with(&format!("{expression}\n")));
+
+        let error = nested(MAX_DEPTH + 1).unwrap_err();
+        assert_eq!(
+            (error.bit, error.kind),
+            (18 * MAX_DEPTH, ErrorKind::TooDeep)
+        );
+        assert_eq!(error.path, [0; MAX_DEPTH]);
+        assert_eq!(nested(2000).unwrap_err().kind, ErrorKind::TooDeep);
+    }
+
+    #[test]
+    fn packet_literal() {
+        let (packet, end) = decode("D2FE28");
+        assert_eq!(packet.version, 6);
+        assert!(matches!(packet.op, Content::Literal(x) if x == 2021));
+        assert!(
+            end == 21,
+            "Bad end return value, expected 21, got {end}\nfrom {:#?}",
+            packet
+        );
+
+        assert!(
+            packet.version_sum() == 6,
+            "Bad sum of packet versions, for {:#?}",
+            packet
+        )
+    }
+
+    #[test]
+    fn packet_operator_length_type_0() {
+        let (packet, _) = decode("38006F45291200");
+        assert_eq!(packet.version, 1);
+        println!("{:#?}", packet);
+
+        // let _expected_sub_packets: Vec<Packet> = vec![
+        //     Packet {
+        //         version: 5,
+        //         op: Content::Literal(10),
+        //     },
+        //     Packet {
+        //         version: 2,
+        //         op: Content::Literal(20),
+        //     },
+        // ];
+
+        // Check packet operator
+        if let Content::Operator(operation, ref sub_packets) = packet.op {
+            assert_eq!(operation, 6); // correct operation
+            assert_eq!(sub_packets.len(), 2); // correct number of subpackets
+
+            // first sub_packet
+            assert_eq!(sub_packets[0].version, 6); // correct version
+
+            // correct literal value
+            assert!(matches!(sub_packets[0].op, Content::Literal(x) if x == 10));
+
+            // second sub_packet
+            assert_eq!(sub_packets[1].version, 2);
+            assert!(matches!(sub_packets[1].op, Content::Literal(x) if x == 20));
+        } else {
+            // can't get here, but in case magic happens, fail the test!
+            panic!("Got bad op {:?}, failing!", packet.op);
+        }
+
+        assert!(
+            packet.version_sum() == 9,
+            "Bad sum of packet versions, for {:#?}",
+            packet
+        )
+    }
+
+    #[test]
+    fn packet_operator_length_type_1() {
+        // Expected Result:
+        // Packet {
+        //     version: 7,
+        //     op: Content::Operator {
+        //         2,
+        //         vec![
+        //             Packet {
+        //                 version: 2,
+        //                 op: Content::Literal(1),
+        //             },
+        //             Packet {
+        //                 version: 4,
+        //                 op: Content::Literal(2),
+        //             },
+        //             Packet {
+        //                 version: 1,
+        //                 op: Content::Literal(3),
+        //             }
+        //          ],
+        //      }
+        // ];
+
+        let (packet, _) = decode("EE00D40C823060");
+
+        assert_eq!(packet.version, 7);
+        // Check packet operator
+        if let Content::Operator(operation, ref sub_packets) = packet.op {
+            assert!(
+                operation == 3,
+                "Incorrect operation value, got {}",
+                operation
+            );
+
+            assert!(
+                sub_packets.len() == 3,
+                "Incorrect number of sub_packets, got {:#?}",
+                sub_packets
+            );
+
+            // first sub_packet
+            assert!(
+                sub_packets[0].version == 2,
+                "Incorrect version in first subpacket, got {:#?}",
+                sub_packets
+            );
+            assert!(
+                matches!(sub_packets[0].op, Content::Literal(x) if x == 1),
+                "Incorrect literal value in first subpacket, got {:#?}",
+                sub_packets
+            );
+
+            // second sub_packet
+            assert!(
+                sub_packets[1].version == 4,
+                "Incorrect version in second subpacket, got {:#?}",
+                sub_packets
+            );
+            assert!(
+                matches!(sub_packets[1].op, Content::Literal(x) if x == 2),
+                "Incorrect Content in second subpacket, got {:#?}",
+                sub_packets
+            );
+
+            // third sub_packet
+            assert!(
+                sub_packets[2].version == 1,
+                "Incorrect version in second subpacket, got {:#?}",
+                sub_packets
+            );
+            assert!(
+                matches!(sub_packets[2].op, Content::Literal(x) if x == 3),
+                "Incorrect Content in third subpacket, got {:#?}",
+                sub_packets
+            );
+        } else {
+            panic!("Got bad op {:#?}, failing!", packet.op);
+        }
+
+        assert!(
+            packet.version_sum() == 14,
+            "Bad sum of packet versions, for {:#?}",
+            packet
+        )
+    }
+
+    #[test]
+    fn nested_operator_3_deep() {
+        let (packet, _) = decode("8A004A801A8002F478");
+
+        assert!(
+            packet.version == 4,
+            "bad version, got {}\n for{:#?}",
+            packet.version,
+            packet,
+        );
+
+        // this functionality should be tested above? consider deletion
+        if let Content::Operator(_operation, ref sub_packets) = packet.op {
+            assert!(
+                sub_packets.len() == 1,
+                "Bad subpacket length, got {}\nfor{:#?}",
+                sub_packets.len(),
+                sub_packets,
+            );
+            // verify subpacket contains a subpacket of type Literal
+            if let Content::Operator(_operation2, ref sub_packets2) = sub_packets[0].op {
+                assert_eq!(sub_packets2.len(), 1);
+                assert_eq!(sub_packets2[0].version, 5);
+
+                if let Content::Operator(_operation3, ref sub_packets3) = sub_packets2[0].op {
+                    assert_eq!(sub_packets3[0].version, 6);
+                    assert!(
+                        std::mem::discriminant(&sub_packets3[0].op)
+                            == std::mem::discriminant(&Content::Literal(0)),
+                        "Unexpected innermost packet type, Expected Content::Literal, got\n{:#?}",
+                        sub_packets3[0]
+                    ); // type, value not checked
+                } else {
+                    panic!(
+                        "subpacket did not have a subpacket as expected, got {:#?}",
+                        sub_packets2[0]
+                    );
+                }
+            } else {
+                panic!(
+                    "subpacket did not have a subpacket as expected, got {:#?}",
+                    sub_packets[0]
+                );
+            }
+        } else {
+            panic!(
+                "Outer Packet expected to contain operator! got {:#?}",
+                packet
+            );
+        }
+
+        assert_eq!(packet.version_sum(), 16);
+    }
+
+    #[test]
+    fn nested_operators_3deep_2wide() {
+        let (packet, _) = decode("620080001611562C8802118E34");
+        assert_eq!(packet.version_sum(), 12);
+    }
+
+    #[test]
+    fn nested_operators_3deep_2pair_2wide_differing_type_id() {
+        let (packet, end) = decode("C0015000016115A2E0802F182340");
+        assert_eq!(packet.version_sum(), 23);
+        assert!(
+            end == 106,
+            "End of Package mismatch. expected 106, got {end}"
+        );
+    }
+
+    #[test]
+    fn nested_operators_3deep_5wide() {
+        let (packet, end) = decode("A0016C880162017C3686B18A3D4780");
+        assert_eq!(packet.version_sum(), 31);
+        assert!(
+            end == 113,
+            "End of Package mismatch. expected 106, got {end}"
+        );
+    }
+
+    #[test]
+    fn packet_sum() {
+        let (packet, _end) = decode("C200B40A82");
+        assert_eq!(packet.execute().unwrap(), 3u64);
+    }
+
+    #[test]
+    fn packet_product() {
+        let (packet, _end) = decode("04005AC33890");
+        assert_eq!(packet.execute().unwrap(), 54u64);
+    }
+
+    #[test]
+    fn packet_min() {
+        let (packet, _end) = decode("880086C3E88112");
+        assert_eq!(packet.execute().unwrap(), 7u64);
+    }
+
+    #[test]
+    fn packet_max() {
+        let (packet, _end) = decode("CE00C43D881120");
+        assert_eq!(packet.execute().unwrap(), 9u64);
+    }
+
+    #[test]
+    fn packet_lessthan() {
+        let (packet, _end) = decode("D8005AC2A8F0");
+        assert_eq!(packet.execute().unwrap(), 1u64);
+    }
+
+    #[test]
+    fn packet_greaterthan() {
+        let (packet, _end) = decode("F600BC2D8F");
+        assert_eq!(packet.execute().unwrap(), 0u64);
+    }
+
+    #[test]
+    fn packet_equalto() {
+        let (packet, _end) = decode("9C005AC2F8F0");
+        assert_eq!(packet.execute().unwrap(), 0u64);
+    }
+
+    #[test]
+    fn packet_equalto_2deep_2pair() {
+        let (packet, _end) = decode("9C0141080250320F1802104A08");
+        assert_eq!(packet.execute().unwrap(), 1u64);
+    }
+
+    #[test]
+    fn outline() {
+        let (packet, _end) = decode("9C005AC2F8F0");
+        assert_eq!(
+            packet_outline(&packet).to_string(),
+            "equal to v4 = 0\n  literal v5 = 5\n  literal v7 = 15\n"
+        );
+    }
+}
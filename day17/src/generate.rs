@@ -0,0 +1,31 @@
+//! Random target areas for `aoc gen`.
+
+use crate::{DEPTH, REACH};
+use common::random::Rng;
+
+/// How far away the real input's target area reaches
+pub const SIZE: usize = 300;
+
+/// A target area reaching out to about `size` and down about a third as
+/// far, within the solver's search. It always covers a spot where a probe
+/// stops moving forward, as the highest shot relies on.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let reach = (size.min(REACH as usize) as i32).max(4);
+    let triangle = |n: i32| n * (n + 1) / 2;
+
+    // Where the probe stops for some starting x velocity
+    let fastest = (1..)
+        .take_while(|&n| triangle(n) <= reach)
+        .last()
+        .unwrap_or(1);
+    let stop = triangle(rng.range((fastest / 2).max(1)..=fastest));
+    let width = rng.range(0..=reach / 8);
+    let x_min = (stop - rng.range(0..=width)).max(1);
+    let x_max = (stop + rng.range(0..=width)).min(REACH);
+
+    let depth = (reach * 3 / 10).clamp(2, DEPTH);
+    let y_min = -rng.range(depth / 2..=depth);
+    let y_max = y_min + rng.range(0..=(-y_min - 1) / 2);
+
+    format!("target area: x={x_min}..{x_max}, y={y_min}..{y_max}\n")
+}
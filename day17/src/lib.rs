@@ -0,0 +1,423 @@
+//! Day 17: Trick Shot. Launching a probe so it lands in a target area.
+//!
+//! General Assumptions:
+//! target is always below the starting position (0,0)
+//! the target always has a positive x value
+
+pub mod generate;
+
+use common::animation::Recorder;
+use common::parse::{range, separated_pair, ParseError, Span};
+use common::random::Rng;
+use common::render::{Cell, Color, Picture};
+use common::{debug, trace, Answer, AocError, Part, Solution};
+use std::ops::{Range, RangeInclusive};
+
+/// The x and y ranges of the target area
+pub type Target = (Range<i32>, Range<i32>);
+
+/// Furthest right [hit_velocities] can reach, in one step
+pub const REACH: i32 = 299;
+
+/// Furthest down [hit_velocities] can reach, in one step
+pub const DEPTH: i32 = 150;
+
+/// Calculate missile trajectory
+/// Drag value of 1 applied to X in either direction while moving
+/// Gravity applies -1 to y velocity each step
+/// just use bruteforce instead of motion equations
+///
+/// Returns every position up to the first one inside the target, or an error
+/// once the probe has fallen past it.
+pub fn trajectory(
+    initial_velocity: (i32, i32),
+    initial_position: (i32, i32),
+    target: &Target,
+) -> Result<Vec<(i32, i32)>, &'static str> {
+    let mut trajectory: Vec<(i32, i32)> = vec![initial_position];
+    let mut velocity = initial_velocity;
+    let mut position = initial_position;
+
+    trace!("targeting {target:?} with {initial_velocity:?}");
+
+    // assume target is always below the starting position
+    while position.1 > target.1.start {
+        // update position
+        position.0 += velocity.0;
+        position.1 += velocity.1;
+
+        // add position to trajectory
+        trajectory.push(position);
+
+        // update velocity
+        if velocity.0 > 0 {
+            velocity.0 -= 1;
+        } else if velocity.0 < 0 {
+            velocity.0 += 1;
+        } // no change if 0
+
+        velocity.1 -= 1;
+
+        // do we need to go further?
+        if target.0.contains(&position.0) && target.1.contains(&position.1) {
+            return Ok(trajectory);
+        }
+    }
+    Err("Miss")
+}
+
+/// Convert the text values to a range, which has to lie within `bounds`
+///
+/// Rust ranges are up-to-noninclusive where the problem is up-to-inclusive, so
+/// the end value is incremented by one.
+fn text_range_to_range(
+    input: Span,
+    name: &str,
+    bounds: RangeInclusive<i32>,
+) -> Result<Range<i32>, ParseError> {
+    let range = range::<i32>(input, name)?;
+    if range.is_empty() || !bounds.contains(range.start()) || !bounds.contains(range.end()) {
+        return Err(input.trim().error(format!(
+            "expected {name} from low to high within {}..{}, where probes are aimed",
+            bounds.start(),
+            bounds.end()
+        )));
+    }
+    Ok(*range.start()..*range.end() + 1)
+}
+
+/// A line like `target area: x=20..30, y=-10..-5`, below and to the right of
+/// the start within reach of [hit_velocities]
+pub fn parse(input: &str) -> Result<Target, ParseError> {
+    let input = Span::new(input).trim();
+    let area = input
+        .strip_prefix("target area:")
+        .ok_or_else(|| input.error("expected \"target area:\""))?;
+    let (x, y) = separated_pair(area, ",")?;
+    Ok((
+        text_range_to_range(x, "x", 1..=REACH)?,
+        text_range_to_range(y, "y", -DEPTH..=-1)?,
+    ))
+}
+
+/// Highest y reached on the way up
+pub fn max_height_in_trajectory(trajectory: &[(i32, i32)]) -> i32 {
+    trace!("trajectory {trajectory:?}");
+    let mut max = i32::MIN;
+    for position in trajectory {
+        if position.1 > max {
+            max = position.1;
+        } else {
+            break;
+        }
+    }
+    trace!("highest point {max}");
+    max
+}
+
+/// Highest y position reachable while still hitting the target
+pub fn max_height_trajectory(target: &Target) -> i32 {
+    // find minimal starting point for x. for max trajectory assume x always
+    // stalls out.
+    // use the triangle equation to find a velocity that will hit after
+    // stalling.
+    let mut x: i32 = 0;
+    while (x * (x + 1) / 2) < target.0.start {
+        x += 1
+    }
+
+    let mut y: i32 = 0;
+    let mut max_y: i32 = 0;
+    let mut traj = trajectory((x, y), (0, 0), target);
+    const MAX_STEPS: u32 = 800;
+    let mut steps = 0u32;
+
+    while steps < MAX_STEPS {
+        if let Ok(traj) = traj {
+            let this_max_y = max_height_in_trajectory(&traj);
+            if this_max_y > max_y {
+                max_y = this_max_y;
+                debug!("new highest point {max_y} with velocity ({x}, {y})");
+            }
+        }
+        y += 1;
+        traj = trajectory((x, y), (0, 0), target);
+        steps += 1;
+    }
+    debug!("stopped searching at velocity ({x}, {y})");
+    max_y
+}
+
+/// Number of initial velocities that hit the target.
+pub fn find_all_hits(target: &Target) -> usize {
+    hit_velocities(target).len()
+}
+
+/// Every initial velocity that hits the target.
+///
+/// Just stupid brute force, I'm sleepy.
+pub fn hit_velocities(target: &Target) -> Vec<(i32, i32)> {
+    let mut hits = Vec::<(i32, i32)>::new();
+
+    for x in 0..=REACH {
+        for y in -DEPTH..DEPTH {
+            if trajectory((x, y), (0, 0), target).is_ok() {
+                hits.push((x, y));
+            }
+        }
+    }
+
+    hits.dedup();
+
+    debug!("{} velocities hit: {hits:?}", hits.len());
+
+    hits
+}
+
+/// Draw trajectories the way the puzzle does, with `S` at the start, `#` at
+/// each probe position and `T` over the target area
+pub fn trajectory_picture(target: &Target, trajectories: &[Vec<(i32, i32)>]) -> Picture {
+    draw_trajectories(target, trajectories, usize::MAX)
+}
+
+/// Record the probes flying along all of `trajectories` at once, one step per
+/// frame, with the whole flight fitting in every frame
+pub fn record_trajectories(
+    target: &Target,
+    trajectories: &[Vec<(i32, i32)>],
+    recorder: &mut dyn Recorder,
+) {
+    let steps = trajectories.iter().map(Vec::len).max().unwrap_or(0);
+    for step in 1..=steps {
+        recorder.frame(&|| draw_trajectories(target, trajectories, step));
+    }
+}
+
+/// [trajectory_picture] with only the first `steps` positions of each
+/// trajectory drawn
+fn draw_trajectories(target: &Target, trajectories: &[Vec<(i32, i32)>], steps: usize) -> Picture {
+    let points = || trajectories.iter().flatten();
+    let x_min = points().map(|x| x.0).fold(target.0.start.min(0), i32::min);
+    let x_max = points().map(|x| x.0).fold(target.0.end - 1, i32::max);
+    let y_min = points().map(|x| x.1).fold(target.1.start.min(0), i32::min);
+    let y_max = points()
+        .map(|x| x.1)
+        .fold(target.1.end.max(1) - 1, i32::max);
+    // Row 0 is the highest point
+    let cell = |(x, y): (i32, i32)| ((y_max - y) as usize, (x - x_min) as usize);
+
+    let mut picture = Picture::new(
+        (x_max - x_min + 1) as usize,
+        (y_max - y_min + 1) as usize,
+        Cell::new('.', Color::BACKGROUND),
+    );
+    for x in target.0.clone() {
+        for y in target.1.clone() {
+            picture.cells[cell((x, y))] = Cell::new('T', Color::BLUE);
+        }
+    }
+    for &point in trajectories.iter().flat_map(|x| x.iter().take(steps)) {
+        picture.cells[cell(point)] = Cell::new('#', Color::YELLOW);
+    }
+    picture.cells[cell((0, 0))] = Cell::new('S', Color::WHITE);
+    picture
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Target;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    /// Highest y position reachable while still hitting the target
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(max_height_trajectory(input).into())
+    }
+
+    /// number of valid velocity pairs
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(find_all_hits(input).into())
+    }
+
+    /// The highest trajectory that hits for part 1, every one for part 2
+    fn picture(input: &Self::Input, part: Part) -> Option<Picture> {
+        Some(trajectory_picture(input, &part_trajectories(input, part)?))
+    }
+
+    /// The same trajectories as [Solution::picture], a step at a time
+    fn animate(input: &Self::Input, part: Part, recorder: &mut dyn Recorder) -> bool {
+        match part_trajectories(input, part) {
+            Some(trajectories) => {
+                record_trajectories(input, &trajectories, recorder);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+/// The highest trajectory that hits for part 1, every one for part 2
+fn part_trajectories(target: &Target, part: Part) -> Option<Vec<Vec<(i32, i32)>>> {
+    let hits: Vec<Vec<(i32, i32)>> = hit_velocities(target)
+        .into_iter()
+        .filter_map(|velocity| trajectory(velocity, (0, 0), target).ok())
+        .collect();
+    match part {
+        Part::One => {
+            let highest = hits
+                .into_iter()
+                .max_by_key(|x| max_height_in_trajectory(x))?;
+            Some(vec![highest])
+        }
+        Part::Two => Some(hits),
+    }
+}
+
+#[cfg(test)]
+/// Tests come from the problem statement examples
+mod tests {
+    use super::*;
+    use common::check::differential;
+    use common::render::Render;
+
+    #[test]
+    fn test_parsing() {
+        let input: Vec<String> = vec!["target area: x=240..292, y=-90..-57".to_string()];
+        assert_eq!((240..292 + 1, -90..-57 + 1), parse(&input[0]).unwrap());
+
+        assert_eq!(
+            (20..30 + 1, -10..-5 + 1),
+            parse(&String::from("target area: x=20..30, y=-10..-5")).unwrap()
+        );
+
+        // sanity check on my understanding of ranges.
+        let test_range = -10..-5 + 1;
+        println!("{test_range:#?}");
+        assert!(test_range.contains(&-10));
+        assert!(test_range.contains(&-5));
+        assert!(!test_range.contains(&-11));
+        assert!(!test_range.contains(&-4));
+
+        for area in [
+            "x=20..300, y=-10..-5",
+            "x=-30..-20, y=-10..-5",
+            "x=20..30, y=5..10",
+        ] {
+            assert!(parse(&format!("target area: {area}")).is_err(), "{area}");
+        }
+        let error = parse("target area: x=20..30, y=-5..-10").unwrap_err();
+        assert_eq!(error.column, 24);
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let target = (20..30 + 1, -10..-5 + 1);
+        let _hit = trajectory((7, 2), (0, 0), &target).unwrap();
+        let _hit = trajectory((6, 3), (0, 0), &target).unwrap();
+        let _hit = trajectory((9, 0), (0, 0), &target).unwrap();
+        let _hit = trajectory((6, 9), (0, 0), &target).unwrap();
+        assert!(trajectory((17, -4), (0, 0), &target).is_err());
+    }
+
+    #[test]
+    fn test_max_in_traj() {
+        let traj = vec![(0, 0), (0, 2), (-1, 3), (32, 4), (2, 3), (2, -12)];
+        assert_eq!(4, max_height_in_trajectory(&traj));
+    }
+
+    #[test]
+    fn test_max_height_trajectory() {
+        let target = (20..30 + 1, -10..-5 + 1);
+        assert_eq!(45, max_height_trajectory(&target));
+    }
+
+    #[test]
+    fn test_all_hits() {
+        let target = (20..30 + 1, -10..-5 + 1);
+        assert_eq!(112, find_all_hits(&target));
+    }
+
+    /// A target area from its inclusive x and y bounds, `None` once shrinking
+    /// has taken it outside the area [hit_velocities] searches, or to where
+    /// no probe stops moving forward inside it as [max_height_trajectory]
+    /// relies on
+    fn searched_target(
+        ((x_min, x_max), (y_min, y_max)): ((i32, i32), (i32, i32)),
+    ) -> Option<Target> {
+        let searched = 0 < x_min && x_min <= x_max && x_max < 300 && -150 <= y_min;
+        let stops = (1..)
+            .map(|n| n * (n + 1) / 2)
+            .take_while(|&stop| stop <= x_max)
+            .any(|stop| stop >= x_min);
+        (searched && stops && y_min <= y_max && y_max < 0)
+            .then(|| (x_min..x_max + 1, y_min..y_max + 1))
+    }
+
+    #[test]
+    fn highest_shot_matches_brute_force() {
+        differential(
+            20,
+            17,
+            |rng| {
+                let (x, y) = parse(&generate::input(rng.range(10..=300), rng)).unwrap();
+                ((x.start, x.end - 1), (y.start, y.end - 1))
+            },
+            |&area| searched_target(area).map(|target| max_height_trajectory(&target)),
+            |&area| {
+                let target = searched_target(area)?;
+                part_trajectories(&target, Part::One)
+                    .map(|highest| max_height_in_trajectory(&highest[0]))
+            },
+        );
+    }
+
+    #[test]
+    fn test_picture() {
+        let target = (20..30 + 1, -10..-5 + 1);
+        let hit = trajectory((7, 2), (0, 0), &target).unwrap();
+        let text =
+            common::render::Ascii { color: false }.render(&trajectory_picture(&target, &[hit]));
+        let expected = "\
+.............#....#............
+.......#..............#........
+...............................
+S........................#.....
+...............................
+...............................
+...........................#...
+...............................
+....................TTTTTTTTTTT
+....................TTTTTTTTTTT
+....................TTTTTTTT#TT
+....................TTTTTTTTTTT
+....................TTTTTTTTTTT
+....................TTTTTTTTTTT
+";
+        assert_eq!(String::from_utf8(text).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_record_trajectories() {
+        let target = (20..30 + 1, -10..-5 + 1);
+        let hits = vec![trajectory((7, 2), (0, 0), &target).unwrap()];
+        let mut frames: Vec<Picture> = Vec::new();
+        record_trajectories(&target, &hits, &mut frames);
+        assert_eq!(frames.len(), hits[0].len());
+        let first = common::render::Ascii { color: false }.render(&frames[0]);
+        assert_eq!(String::from_utf8(first).unwrap().matches('#').count(), 0);
+        let last = frames.last().unwrap();
+        assert_eq!(
+            common::render::Ascii { color: false }.render(last),
+            common::render::Ascii { color: false }.render(&trajectory_picture(&target, &hits))
+        );
+    }
+}
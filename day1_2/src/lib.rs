@@ -0,0 +1,46 @@
+//! Day 1 with both parts, see [day1] for parsing and part 1.
+
+use common::random::Rng;
+use common::{trace, Answer, AocError, Solution};
+use day1::generate;
+
+/// Same as [day1::part1], but compare the sums of a sliding 3 measurement
+/// window to smooth out the noise
+pub fn window_increases(data: &[i64]) -> u64 {
+    let mut increases: u64 = 0;
+    let mut old: i64 = i64::MAX;
+
+    for window in data.windows(3) {
+        let val: i64 = window.iter().sum();
+
+        trace!("window sum {val}, increased: {}", val > old);
+        if val > old {
+            increases += 1;
+        }
+        old = val;
+    }
+    increases
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(day1::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(day1::part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(window_increases(input).into())
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
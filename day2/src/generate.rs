@@ -0,0 +1,21 @@
+//! Random courses for `aoc gen`.
+
+use common::random::Rng;
+
+/// Commands in the real input
+pub const SIZE: usize = 1000;
+
+/// `size` commands like `forward 5`. Going down is likelier than going up, so
+/// the submarine stays under water.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+    for _ in 0..size {
+        let direction = match rng.below(8) {
+            0..=3 => "forward",
+            4..=6 => "down",
+            _ => "up",
+        };
+        text.push_str(&format!("{direction} {}\n", rng.range(1..=9)));
+    }
+    text
+}
@@ -0,0 +1,75 @@
+//! Day 2: Dive! Steering the submarine with a list of commands.
+
+pub mod generate;
+
+use common::parse::{separated_pair, ParseError, Span};
+use common::{debug, AocError};
+
+/// Which way a command moves the submarine
+#[derive(Debug)]
+pub enum Direction {
+    Forward,
+    Up,
+    Down,
+}
+
+/// One line of the course, like `forward 5`
+#[derive(Debug)]
+pub struct Command {
+    pub direction: Direction,
+    /// How far to move
+    pub magnitude: i64,
+}
+
+impl Command {
+    /// Parse a line like `forward 5`
+    pub fn from_span(line: Span) -> Result<Command, ParseError> {
+        let (direction, magnitude) = separated_pair(line.trim(), " ")?;
+        Ok(Command {
+            direction: parse_direction(direction)?,
+            magnitude: magnitude.parse()?,
+        })
+    }
+}
+
+/// `forward`, `up` or `down`, in any case
+fn parse_direction(text: Span) -> Result<Direction, ParseError> {
+    match text.as_str().to_lowercase().as_str() {
+        "forward" => Ok(Direction::Forward),
+        "up" => Ok(Direction::Up),
+        "down" => Ok(Direction::Down),
+        _ => Err(text.error("expected forward, up or down")),
+    }
+}
+
+/// One command per line
+pub fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+    let commands = Span::new(input)
+        .lines()
+        .map(Command::from_span)
+        .collect::<Result<Vec<Command>, ParseError>>()?;
+    debug!("{} commands", commands.len());
+    Ok(commands)
+}
+
+/// The error for a course that goes further than an i64 can count
+pub fn too_far() -> AocError {
+    AocError::Semantic("the course goes too far to count".to_owned())
+}
+
+/// Horizontal position times depth after following the course
+pub fn part1(commands: &[Command]) -> Result<i64, AocError> {
+    let mut forward: i64 = 0;
+    let mut depth: i64 = 0;
+
+    for command in commands {
+        let magnitude = command.magnitude;
+        match command.direction {
+            Direction::Forward => forward = forward.checked_add(magnitude).ok_or_else(too_far)?,
+            Direction::Down => depth = depth.checked_add(magnitude).ok_or_else(too_far)?,
+            Direction::Up => depth = depth.checked_sub(magnitude).ok_or_else(too_far)?,
+        }
+    }
+
+    forward.checked_mul(depth).ok_or_else(too_far)
+}
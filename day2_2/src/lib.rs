@@ -0,0 +1,75 @@
+//! Day 2 with both parts, see [day2] for parsing and part 1.
+
+use common::random::Rng;
+use common::{Answer, AocError, Solution};
+use day2::generate;
+use day2::{too_far, Command, Direction};
+
+/// Horizontal position times depth when up and down steer the aim and forward
+/// moves along it
+pub fn follow_aim(commands: &[Command]) -> Result<i64, AocError> {
+    let mut forward: i64 = 0;
+    let mut depth: i64 = 0;
+    let mut aim: i64 = 0;
+
+    for command in commands {
+        let magnitude = command.magnitude;
+        match command.direction {
+            Direction::Forward => {
+                forward = forward.checked_add(magnitude).ok_or_else(too_far)?;
+                depth = aim
+                    .checked_mul(magnitude)
+                    .and_then(|x| depth.checked_add(x))
+                    .ok_or_else(too_far)?;
+            }
+            Direction::Down => aim = aim.checked_add(magnitude).ok_or_else(too_far)?,
+            Direction::Up => aim = aim.checked_sub(magnitude).ok_or_else(too_far)?,
+        }
+    }
+
+    forward.checked_mul(depth).ok_or_else(too_far)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Command>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(day2::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(day2::part1(input)?.into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(follow_aim(input)?.into())
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn courses_too_far_to_count() {
+        let commands = day2::parse("forward 9223372036854775807\ndown 2").unwrap();
+        assert!(day2::part1(&commands).is_err());
+        let commands = day2::parse("down 2\nforward 9223372036854775807").unwrap();
+        assert!(follow_aim(&commands).is_err());
+
+        let commands = day2::parse("down 9223372036854775807\nup 1\nforward 2").unwrap();
+        assert!(day2::part1(&commands).is_err());
+        assert!(follow_aim(&commands).is_err());
+
+        let commands = day2::parse("forward 5\ndown 5\nforward 8\nup 3").unwrap();
+        assert_eq!(day2::part1(&commands).unwrap(), 26);
+        assert_eq!(follow_aim(&commands).unwrap(), 13 * 40);
+    }
+}
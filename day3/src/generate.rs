@@ -0,0 +1,31 @@
+//! Random diagnostic reports for `aoc gen`.
+
+use common::random::Rng;
+use std::collections::HashSet;
+
+/// Numbers in the real input
+pub const SIZE: usize = 1000;
+
+/// `size` different binary numbers, 12 bits long unless there are too many to
+/// fit. The ratings need at least two numbers and both first bits, so there
+/// are always two numbers starting with 0 and 1.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(2);
+    let width = (usize::BITS - (size - 1).leading_zeros()).max(12);
+    let high = 1u64 << (width - 1);
+
+    let mut numbers: Vec<u64> = vec![rng.below(high), high + rng.below(high)];
+    let mut seen: HashSet<u64> = numbers.iter().copied().collect();
+    while numbers.len() < size {
+        let number = rng.below(high << 1);
+        if seen.insert(number) {
+            numbers.push(number);
+        }
+    }
+    rng.shuffle(&mut numbers);
+
+    numbers
+        .iter()
+        .map(|x| format!("{x:0width$b}\n", width = width as usize))
+        .collect()
+}
@@ -0,0 +1,70 @@
+//! Day 3: Binary Diagnostic. Rates the submarine from a report of binary
+//! numbers.
+
+pub mod generate;
+
+use common::debug;
+use common::parse::{ParseError, Span};
+
+/// Most digits a number can have, so the product of two still fits in 64 bits
+pub const MAX_DIGITS: usize = 32;
+
+/// The diagnostic report as rows of `'0'` and `'1'` chars, one per line
+pub fn parse(input: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    let mut grid: Vec<Vec<char>> = Vec::new();
+    for line in Span::new(input).lines() {
+        let mut row: Vec<char> = Vec::new();
+        for (c, span) in line.chars() {
+            match c {
+                '0' | '1' => row.push(c),
+                _ => return Err(span.error("expected a binary digit")),
+            }
+        }
+        if row.is_empty() || grid.first().is_some_and(|first| first.len() != row.len()) {
+            return Err(line.error("expected a binary number as long as the first line"));
+        }
+        if row.len() > MAX_DIGITS {
+            return Err(line.error(format!(
+                "expected a binary number of at most {MAX_DIGITS} digits"
+            )));
+        }
+        grid.push(row);
+    }
+    match grid.is_empty() {
+        true => Err(Span::new(input).error("expected a diagnostic report")),
+        false => Ok(grid),
+    }
+}
+
+/// Power consumption, gamma * epsilon
+pub fn part1(grid: &[Vec<char>]) -> u64 {
+    // greater than floor of half the values
+    let threshold: u64 = grid.len() as u64 >> 1;
+
+    // count '0's and '1's in each column
+    let mut zeroes: Vec<u64> = vec![0; grid[0].len()];
+    for row in grid {
+        for (index, &value) in row.iter().enumerate() {
+            if value == '0' {
+                zeroes[index] += 1;
+            }
+        }
+    }
+
+    // Each gamma bit is determined by whichever bit has a higher count in the
+    // input (epsilon is the inverse value of gamma)
+    let mut gamma: u64 = 0;
+    let mut epsilon: u64 = 0;
+    for count in zeroes {
+        gamma <<= 1;
+        epsilon <<= 1;
+        if count <= threshold {
+            // More 1's than zeroes
+            gamma += 1;
+        } else {
+            epsilon += 1;
+        }
+    }
+    debug!("gamma {gamma}, epsilon {epsilon}");
+    gamma * epsilon
+}
@@ -0,0 +1,146 @@
+//! Day 3 with both parts, see [day3] for parsing and part 1.
+
+use common::random::Rng;
+use common::{debug, Answer, AocError, Solution};
+use day3::generate;
+
+/// Which bit is the most common in a column
+#[derive(Debug)]
+enum Criterion {
+    Low,
+    High,
+    Ambiguous,
+    AllSame,
+}
+
+/// Whether a rating keeps the rows with the most or least common bit
+enum Commonality {
+    Most,
+    Least,
+}
+
+/// Most common bit in the column at `index`
+fn get_most_common(index: usize, grid: &[Vec<char>]) -> Criterion {
+    let threshold: u64 = grid.len() as u64 >> 1;
+
+    let mut zeroes: u64 = 0;
+
+    for row in grid {
+        if row[index] == '0' {
+            zeroes += 1;
+        }
+    }
+
+    if (zeroes == 0) || (zeroes == grid.len() as u64) {
+        Criterion::AllSame
+    } else if zeroes > threshold {
+        Criterion::Low
+    } else if zeroes < threshold {
+        Criterion::High
+    } else {
+        Criterion::Ambiguous
+    }
+}
+
+/// Filter `grid` column by column from the second, starting the rating at `iv`
+/// (the first column's bit)
+fn find_vec(iv: u64, commonality: Commonality, grid: &mut Vec<Vec<char>>) -> u64 {
+    let mut value = iv;
+    let row_len = grid[0].len();
+    for col_index in 1..row_len {
+        let temp = get_most_common(col_index, grid);
+        let criterion = match temp {
+            Criterion::Low => match commonality {
+                Commonality::Most => '0',
+                Commonality::Least => '1',
+            },
+            Criterion::High => match commonality {
+                Commonality::Most => '1',
+                Commonality::Least => '0',
+            },
+            Criterion::Ambiguous => match commonality {
+                Commonality::Most => '1',
+                Commonality::Least => '0',
+            },
+            Criterion::AllSame => grid[0][col_index],
+        };
+
+        // Update the value, "decoding" the bit string
+        value <<= 1;
+        if criterion == '1' {
+            value += 1;
+        }
+
+        // traverse the rows, kicking out filtered rows. There's some optimization
+        // opportunity to start from the end of the grid and work forwards
+        // since the remove method has to shift the vector to the left anytime
+        // something gets kicked out.
+        //
+        // Note: This can be replaced with the drain_filter method once
+        // stabilized
+        let mut row_index = 0;
+        while row_index < grid.len() {
+            if grid[row_index][col_index] != criterion {
+                let _ = grid.remove(row_index);
+            } else {
+                row_index += 1;
+            }
+        }
+    }
+    value
+}
+
+/// Life support rating, O2 rating * CO2 rating.
+///
+/// Each rating is the number left after repeatedly keeping only the rows with
+/// the most (O2) or least (CO2) common bit in the next column.
+pub fn life_support_rating(report: &[Vec<char>]) -> u64 {
+    // When every row starts the same, both ratings keep all of them
+    let (o2_first_criterion, co2_first_criterion) = match get_most_common(0, report) {
+        Criterion::Low => ('0', '1'),
+        Criterion::High | Criterion::Ambiguous => ('1', '0'),
+        Criterion::AllSame => (report[0][0], report[0][0]),
+    };
+
+    // split rows into those that could be used for either o2 or co2 values
+    let starting = |criterion| -> Vec<Vec<char>> {
+        report
+            .iter()
+            .filter(|row| row[0] == criterion)
+            .cloned()
+            .collect()
+    };
+    let mut o2 = starting(o2_first_criterion);
+    let mut co2 = starting(co2_first_criterion);
+
+    // get ratings
+    let bit = |criterion| (criterion == '1') as u64;
+    let o2_rating = find_vec(bit(o2_first_criterion), Commonality::Most, &mut o2);
+    let co2_rating = find_vec(bit(co2_first_criterion), Commonality::Least, &mut co2);
+
+    debug!("O2 rating {o2_rating}, CO2 rating {co2_rating}");
+    o2_rating * co2_rating
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(day3::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(day3::part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(life_support_rating(input).into())
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
@@ -0,0 +1,30 @@
+//! Random bingo games for `aoc gen`.
+
+use common::random::Rng;
+
+/// Boards in the real input
+pub const SIZE: usize = 100;
+
+/// Every number from 0 to 99 gets called, so every board wins eventually
+const NUMBERS: u32 = 100;
+
+/// The callout numbers in a random order, then `size` boards of 25 different
+/// numbers each
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut numbers: Vec<u32> = (0..NUMBERS).collect();
+    rng.shuffle(&mut numbers);
+    let callouts: Vec<String> = numbers.iter().map(u32::to_string).collect();
+    let mut text = callouts.join(",");
+    text.push('\n');
+
+    for _ in 0..size.max(1) {
+        rng.shuffle(&mut numbers);
+        text.push('\n');
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<String> = row.iter().map(|x| format!("{x:2}")).collect();
+            text.push_str(&row.join(" "));
+            text.push('\n');
+        }
+    }
+    text
+}
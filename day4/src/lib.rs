@@ -0,0 +1,290 @@
+//! Day 4: Giant Squid. Playing bingo to find the first and last boards to
+//! win.
+
+pub mod generate;
+
+use common::parse::{csv_numbers, sections, ParseError, Span};
+use common::random::Rng;
+use common::{debug, trace, Answer, AocError, Json, Part, Solution};
+
+/// Rows and columns on a board
+pub const SIZE: usize = 5;
+
+/// Position of a called number on a board
+#[derive(Clone, Copy, Debug)]
+struct Mark {
+    row: usize,
+    col: usize,
+}
+
+/// A 5x5 board along with the numbers marked on it so far
+#[derive(Clone, Debug)]
+pub struct BingoBoard {
+    board: Vec<Vec<u32>>,
+    marks: Vec<Mark>,
+    won: bool,
+}
+
+/// A board as it won
+#[derive(Debug)]
+pub struct WinningBoard {
+    /// Sum of the unmarked numbers times the number that won
+    pub score: u64,
+    /// The completed row or column
+    pub win_marks: Vec<u32>,
+}
+
+impl BingoBoard {
+    /// An empty board, see [BingoBoard::from_rows]
+    pub fn new() -> Self {
+        BingoBoard {
+            board: Vec::new(),
+            marks: Vec::new(),
+            won: false,
+        }
+    }
+
+    /// Board with these rows and nothing marked.
+    ///
+    /// Panics unless there are [SIZE] rows of [SIZE] numbers.
+    pub fn from_rows(rows: Vec<Vec<u32>>) -> Self {
+        assert!(
+            rows.len() == SIZE && rows.iter().all(|row| row.len() == SIZE),
+            "a board has {SIZE} rows of {SIZE} numbers"
+        );
+        BingoBoard {
+            board: rows,
+            ..Self::new()
+        }
+    }
+
+    pub fn rows(&self) -> &[Vec<u32>] {
+        &self.board
+    }
+
+    /// Mark `num` wherever it is on the board, returns whether it was there.
+    /// A number called again doesn't mark anything more.
+    pub fn mark(&mut self, num: u32) -> bool {
+        let mut hit: bool = false;
+        for (row_index, row) in self.board.iter().enumerate() {
+            for (col_index, value) in row.iter().enumerate() {
+                let marked = self
+                    .marks
+                    .iter()
+                    .any(|mark| (mark.row, mark.col) == (row_index, col_index));
+                if *value == num && !marked {
+                    self.marks.push(Mark {
+                        row: row_index,
+                        col: col_index,
+                    });
+                    hit = true;
+                }
+            }
+        }
+        hit
+    }
+
+    /// The numbers of a fully marked row or column, if there is one
+    pub fn winning_values(&self) -> Option<Vec<u32>> {
+        if self.marks.len() < SIZE {
+            // Not enough marks to win, bail early
+            return None;
+        }
+
+        let mut row_hits: Vec<u32> = vec![0; SIZE];
+        let mut col_hits: Vec<u32> = vec![0; SIZE];
+
+        for mark in self.marks.iter() {
+            row_hits[mark.row] += 1;
+            col_hits[mark.col] += 1;
+        }
+
+        for (row_index, value) in row_hits.iter().enumerate() {
+            if *value >= SIZE as u32 {
+                let mut retval: Vec<u32> = Vec::with_capacity(SIZE);
+                for markval in self.board[row_index].iter() {
+                    retval.push(*markval);
+                }
+                return Some(retval);
+            }
+        }
+
+        for (col_index, value) in col_hits.iter().enumerate() {
+            if *value >= SIZE as u32 {
+                let mut retval: Vec<u32> = Vec::with_capacity(SIZE);
+                for i in 0..SIZE {
+                    retval.push(self.board[i][col_index]);
+                }
+                return Some(retval);
+            }
+        }
+        None
+    }
+
+    /// Sum of the unmarked numbers times the last number marked.
+    ///
+    /// Panics if nothing has been marked yet, fails if the score doesn't fit
+    /// in a u64.
+    pub fn score(&self) -> Result<u64, AocError> {
+        let mut score: u64 = 0;
+        for row in self.board.iter() {
+            score += row.iter().map(|&x| x as u64).sum::<u64>();
+        }
+        for mark in self.marks.iter() {
+            score -= self.board[mark.row][mark.col] as u64;
+        }
+        let last_mark = self.marks.last().unwrap();
+        score
+            .checked_mul(self.board[last_mark.row][last_mark.col] as u64)
+            .ok_or_else(|| AocError::Semantic("a board's score is too big".to_owned()))
+    }
+}
+
+impl Default for BingoBoard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The called numbers and the boards they're played on
+#[derive(Debug)]
+pub struct Bingo {
+    /// Numbers in the order they're called
+    pub callouts: Vec<u32>,
+    pub boards: Vec<BingoBoard>,
+}
+
+impl Bingo {
+    /// Play the game on fresh copies of the boards, returning the boards in
+    /// the order that they won.
+    pub fn play(&self) -> Result<Vec<WinningBoard>, AocError> {
+        let mut boards = self.boards.clone();
+        let mut winners: Vec<WinningBoard> = Vec::new();
+        for callout in &self.callouts {
+            for board in &mut boards {
+                if !board.won {
+                    board.mark(*callout);
+                    if let Some(vals) = board.winning_values() {
+                        let score = board.score()?;
+                        debug!("{callout} wins with {vals:?}, score {score}");
+                        winners.push(WinningBoard {
+                            score,
+                            win_marks: vals,
+                        });
+                        board.won = true;
+                    }
+                }
+            }
+        }
+        Ok(winners)
+    }
+}
+
+/// Callout numbers followed by each of the boards, separated by blank lines
+pub fn parse(input: &str) -> Result<Bingo, ParseError> {
+    let sections = sections(input);
+    let (callouts, boards) = match sections.split_first() {
+        Some(x) => x,
+        None => return Err(Span::new(input).error("expected callout numbers")),
+    };
+
+    let callouts: Vec<u32> = csv_numbers(*callouts)?;
+    debug!("{} numbers called", callouts.len());
+
+    let mut parsed: Vec<BingoBoard> = Vec::new();
+    for group in boards {
+        let rows = group
+            .lines()
+            .map(|row| {
+                let numbers = row
+                    .split_whitespace()
+                    .map(|x| x.parse::<u32>())
+                    .collect::<Result<Vec<u32>, ParseError>>()?;
+                match numbers.len() {
+                    SIZE => Ok(numbers),
+                    _ => Err(row.error(format!("expected a row of {SIZE} numbers"))),
+                }
+            })
+            .collect::<Result<Vec<Vec<u32>>, ParseError>>()?;
+        if rows.len() != SIZE {
+            return Err(group.error(format!("expected a board of {SIZE} rows")));
+        }
+        parsed.push(BingoBoard::from_rows(rows));
+    }
+    debug!("{} boards", parsed.len());
+    trace!("boards {parsed:?}");
+
+    Ok(Bingo {
+        callouts,
+        boards: parsed,
+    })
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Bingo;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    /// Score of the first board to win
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        match input.play()?.first() {
+            Some(board) => Ok(board.score.into()),
+            None => Err(AocError::Semantic("no board wins".to_owned())),
+        }
+    }
+
+    /// Score of the last board to win
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        match input.play()?.last() {
+            Some(board) => Ok(board.score.into()),
+            None => Err(AocError::Semantic("no board wins".to_owned())),
+        }
+    }
+
+    /// The winning board's score and the marked numbers that won it
+    fn extras(input: &Self::Input, part: Part) -> Option<Json> {
+        let winners = input.play().ok()?;
+        let board = match part {
+            Part::One => winners.first(),
+            Part::Two => winners.last(),
+        }?;
+        Some(Json::object([
+            ("score", board.score.into()),
+            ("win_marks", board.win_marks.clone().into()),
+        ]))
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD: &str = "1 2 3 4 5\n6 7 8 9 10\n11 12 13 14 15\n16 17 18 19 20\n21 22 23 24 25";
+
+    #[test]
+    fn boards_are_5_by_5() {
+        let error = parse(&format!("1\n\n{BOARD} 26")).unwrap_err();
+        assert_eq!((error.line, error.column), (7, 1));
+        let error = parse(&format!("1\n\n{BOARD}\n26 27 28 29 30")).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert!(parse(&format!("1\n\n{BOARD}")).is_ok());
+    }
+
+    #[test]
+    fn numbers_called_twice() {
+        let bingo = parse(&format!("1,1,1,1,1\n\n{BOARD}")).unwrap();
+        assert!(Day4::part1(&bingo).is_err());
+
+        let bingo = parse(&format!("1,1,2,3,4,5\n\n{BOARD}")).unwrap();
+        assert_eq!(Day4::part1(&bingo).unwrap(), Answer::Number(310 * 5));
+    }
+}
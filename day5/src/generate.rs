@@ -0,0 +1,34 @@
+//! Random vent lines for `aoc gen`.
+
+use common::random::Rng;
+
+/// Lines in the real input
+pub const SIZE: usize = 500;
+
+/// Coordinates are below this, like the real input's
+const EXTENT: i64 = 1000;
+
+/// `size` lines like `0,9 -> 5,9`, each horizontal, vertical or diagonal at
+/// 45 degrees
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+    for _ in 0..size {
+        let (x1, y1) = (rng.range(0..=EXTENT - 1), rng.range(0..=EXTENT - 1));
+        let (x2, y2) = match rng.below(3) {
+            0 => (rng.range(0..=EXTENT - 1), y1),
+            1 => (x1, rng.range(0..=EXTENT - 1)),
+            _ => {
+                // Go as far as fits both ways along a random diagonal
+                let (dx, dy) = (rng.range(0..=1) * 2 - 1, rng.range(0..=1) * 2 - 1);
+                let room = |start: i64, step: i64| match step {
+                    1 => EXTENT - 1 - start,
+                    _ => start,
+                };
+                let length = rng.range(0..=room(x1, dx).min(room(y1, dy)));
+                (x1 + dx * length, y1 + dy * length)
+            }
+        };
+        text.push_str(&format!("{x1},{y1} -> {x2},{y2}\n"));
+    }
+    text
+}
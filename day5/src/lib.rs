@@ -0,0 +1,143 @@
+//! Day 5: Hydrothermal Venture. Counting where lines of vents overlap.
+
+pub mod generate;
+
+use common::parse::{separated_pair, ParseError, Span};
+use common::render::{Cell, Color, Picture};
+use common::{trace, Grid};
+
+/// Coordinates are below this, so the map of every point stays small enough
+/// to hold
+pub const MAX_COORDINATE: u32 = 1 << 12;
+
+/// A spot on the ocean floor
+#[derive(Debug)]
+pub struct Point {
+    pub x: u32,
+    pub y: u32,
+}
+
+/// A line of hydrothermal vents, including both ends
+#[derive(Debug)]
+pub struct Line {
+    pub start: Point,
+    pub end: Point,
+}
+
+/// Parse the input data into lines
+///
+/// Lines in format "start.x,start.y -> end.x,end.y"
+pub fn parse(input: &str) -> Result<Vec<Line>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(|line| {
+            let (start, end) = separated_pair(line, "->")?;
+            Ok(Line {
+                start: parse_point(start)?,
+                end: parse_point(end)?,
+            })
+        })
+        .collect()
+}
+
+/// A point like `0,9`
+fn parse_point(text: Span) -> Result<Point, ParseError> {
+    let (x, y) = separated_pair(text, ",")?;
+    Ok(Point {
+        x: parse_coordinate(x)?,
+        y: parse_coordinate(y)?,
+    })
+}
+
+fn parse_coordinate(text: Span) -> Result<u32, ParseError> {
+    match text.parse()? {
+        coordinate if coordinate < MAX_COORDINATE => Ok(coordinate),
+        _ => Err(text.error(format!("expected a coordinate below {MAX_COORDINATE}"))),
+    }
+}
+
+/// Create a zeroed map big enough to hold every line
+pub fn empty_graph(lines: &[Line]) -> Grid<u32> {
+    let mut max_x: u32 = 0;
+    let mut max_y: u32 = 0;
+
+    // There's probably a better way to do this
+    for line in lines {
+        if line.start.x > max_x {
+            max_x = line.start.x;
+        }
+        if line.end.x > max_x {
+            max_x = line.end.x;
+        }
+        if line.start.y > max_y {
+            max_y = line.start.y;
+        }
+        if line.end.y > max_y {
+            max_y = line.end.y;
+        }
+    }
+
+    Grid::new((max_x + 1) as usize, (max_y + 1) as usize, 0)
+}
+
+/// Count the points where at least two lines overlap
+pub fn junctions(graph: &Grid<u32>) -> u32 {
+    trace!("vents\n{graph}");
+    graph.iter().filter(|&&col| col > 1).count() as u32
+}
+
+/// Draw the number of vents at each point, hotter where more lines overlap
+pub fn vent_picture(graph: &Grid<u32>) -> Picture {
+    Picture::from_grid(graph, |&count| match count {
+        0 => Cell::new('.', Color::GRAY),
+        1 => Cell::new('1', Color::BLUE),
+        2..=9 => Cell::new(
+            char::from_digit(count, 10).unwrap(),
+            Color::YELLOW.mix(Color::RED, (count - 2) as f32 / 3.0),
+        ),
+        _ => Cell::new('+', Color::RED),
+    })
+}
+
+/// Overlaps of the horizontal and vertical lines only
+pub fn part1(lines: &[Line]) -> u32 {
+    junctions(&straight_vents(lines))
+}
+
+/// Number of horizontal and vertical lines covering each point
+pub fn straight_vents(lines: &[Line]) -> Grid<u32> {
+    let mut graph = empty_graph(lines);
+
+    for line in lines {
+        if line.start.x == line.end.x {
+            match line.start.y < line.end.y {
+                true => {
+                    for y in line.start.y..(line.end.y + 1) {
+                        graph[(y as usize, line.start.x as usize)] += 1;
+                    }
+                }
+                false => {
+                    for y in line.end.y..(line.start.y + 1) {
+                        graph[(y as usize, line.start.x as usize)] += 1;
+                    }
+                }
+            }
+        }
+        if line.start.y == line.end.y {
+            match line.start.x < line.end.x {
+                true => {
+                    for x in line.start.x..(line.end.x + 1) {
+                        graph[(line.start.y as usize, x as usize)] += 1;
+                    }
+                }
+                false => {
+                    for x in line.end.x..(line.start.x + 1) {
+                        graph[(line.start.y as usize, x as usize)] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    graph
+}
@@ -0,0 +1,66 @@
+//! Day 5 with both parts, see [day5] for parsing and part 1.
+
+use common::random::Rng;
+use common::render::Picture;
+use common::{Answer, AocError, Grid, Part, Solution};
+use day5::generate;
+use day5::Line;
+
+/// Overlaps including the 45 degree diagonal lines
+pub fn diagonal_junctions(lines: &[Line]) -> u32 {
+    day5::junctions(&all_vents(lines))
+}
+
+/// Number of lines covering each point, including the diagonals
+pub fn all_vents(lines: &[Line]) -> Grid<u32> {
+    let mut graph = day5::empty_graph(lines);
+
+    for line in lines {
+        let delta_x: i32 = line.end.x as i32 - line.start.x as i32;
+        let delta_y: i32 = line.end.y as i32 - line.start.y as i32;
+        if delta_x == 0 || delta_y == 0 || (delta_x.abs() == delta_y.abs()) {
+            let mut x: u32 = line.start.x;
+            let mut y: u32 = line.start.y;
+            graph[(y as usize, x as usize)] += 1;
+            while (x != line.end.x) || (y != line.end.y) {
+                x = (x as i32 + delta_x.signum()) as u32;
+                y = (y as i32 + delta_y.signum()) as u32;
+                graph[(y as usize, x as usize)] += 1;
+            }
+        }
+    }
+
+    graph
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = Vec<Line>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(day5::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(day5::part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(diagonal_junctions(input).into())
+    }
+
+    /// How many vents cover each point, with diagonals only in part 2
+    fn picture(input: &Self::Input, part: Part) -> Option<Picture> {
+        let graph = match part {
+            Part::One => day5::straight_vents(input),
+            Part::Two => all_vents(input),
+        };
+        Some(day5::vent_picture(&graph))
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
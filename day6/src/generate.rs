@@ -0,0 +1,16 @@
+//! Random schools of lanternfish for `aoc gen`.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use common::random::Rng;
+
+/// Fish in the real input
+pub const SIZE: usize = 300;
+
+/// `size` timers from 1 to 5 on one line, like the real input's
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let timers: Vec<String> = (0..size.max(1))
+        .map(|_| rng.range(1u32..=5).to_string())
+        .collect();
+    timers.join(",") + "\n"
+}
@@ -0,0 +1,80 @@
+//! Day 6: Lanternfish. How fast a school of fish grows.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod generate;
+
+use alloc::format;
+use alloc::vec::Vec;
+use common::parse::{ParseError, Span};
+use common::trace;
+
+/// Most days a fish can have until it spawns, a newborn's
+pub const MAX_DAYS: u32 = 8;
+
+/// A fish and the days until it next spawns
+pub struct Fish {
+    days: u32,
+}
+
+impl Fish {
+    /// A newborn fish, which takes 2 extra days for its first spawn
+    pub fn new() -> Self {
+        Fish { days: MAX_DAYS }
+    }
+
+    /// Count down a day, returns whether a new fish was born
+    pub fn spawn(&mut self) -> bool {
+        if self.days == 0 {
+            self.days = 6;
+            return true;
+        }
+        self.days -= 1;
+        false
+    }
+}
+
+impl Default for Fish {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Days until each fish spawns. Only one row in input
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    Span::new(input)
+        .trim()
+        .split(",")
+        .map(|days| {
+            let days = days.trim();
+            match days.parse()? {
+                count if count <= MAX_DAYS => Ok(count),
+                _ => Err(days.error(format!("expected at most {MAX_DAYS} days"))),
+            }
+        })
+        .collect()
+}
+
+/// Simulate every fish individually, returns the number of fish after `days`
+pub fn simulate(ages: &[u32], days: u32) -> usize {
+    let mut fishes: Vec<Fish> = ages.iter().map(|&days| Fish { days }).collect();
+
+    let mut new_fishes: Vec<Fish> = Vec::new();
+    for day in 1..(days + 1) {
+        for fish in &mut fishes {
+            if fish.spawn() {
+                new_fishes.push(Fish::new());
+            }
+        }
+        fishes.append(&mut new_fishes);
+        trace!("day {day}: {} fish", fishes.len());
+    }
+    fishes.len()
+}
+
+/// Fish after 80 days
+pub fn part1(ages: &[u32]) -> usize {
+    simulate(ages, 80)
+}
@@ -1,48 +1,19 @@
-struct Fish {
-    days: u32,
-}
-
-impl Fish {
-    pub fn new() -> Self {
-        Fish { days: 8 }
-    }
-
-    pub fn spawn(&mut self) -> bool {
-        if self.days == 0 {
-            self.days = 6;
-            return true;
-        }
-        self.days -= 1;
-        return false;
-    }
-}
-
-fn main() {
-    // Get input
-    //let inputdata = match common::read_input("../test_input.txt") {
-    let inputdata = match common::read_input("../input.txt") {
-        Ok(val) => val,
-        Err(e) => panic!("Error reading input file! {}", e),
-    };
-
-    // only one row in input
-
-    let mut fishes: Vec<Fish> = inputdata[0]
-        .split(",")
-        .map(|x| Fish {
-            days: x.parse::<u32>().unwrap(),
-        })
-        .collect();
+use common::{Format, Part};
+use std::process::ExitCode;
+use std::time::Instant;
 
-    let mut new_fishes: Vec<Fish> = Vec::new();
-    for day in 1..(80 + 1) {
-        //for day in 1..(256 + 1) {
-        for fish in &mut fishes {
-            if fish.spawn() == true {
-                new_fishes.push(Fish::new());
-            }
+fn main() -> ExitCode {
+    // Get input, pass `test` to use test_input.txt instead
+    common::run_main(6, |inputdata, options| {
+        let ages = day6::parse(inputdata)?;
+        let start = Instant::now();
+        let fish = day6::part1(&ages);
+        match options.format {
+            Format::Text => println!("day: 80, fish: {fish}"),
+            Format::Json => common::print_record(6, Part::One, &fish.into(), start.elapsed(), None),
         }
-        fishes.append(&mut new_fishes);
-        println!("day: {day}, fish: {}", fishes.len());
-    }
+        // Nothing to draw or animate, so asking to is an error
+        options.render(|| None)?;
+        options.animate(|_| false)
+    })
 }
@@ -0,0 +1,115 @@
+//! Day 6 with both parts, see [day6] for parsing and part 1.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use common::animation::Recorder;
+use common::random::Rng;
+use common::render::{log_bars, Color, Picture};
+use common::{trace, Answer, AocError, Part, Solution};
+use day6::generate;
+
+/// Count the fish after `days` by tracking how many fish are N days old
+/// instead of each fish.
+pub fn simulate(ages: &[u32], days: u32) -> u64 {
+    simulate_recorded(ages, days, &mut ())
+}
+
+/// [simulate], recording how many fish have each number of days left at the
+/// start and after every day
+pub fn simulate_recorded(ages: &[u32], days: u32, recorder: &mut dyn Recorder) -> u64 {
+    // Create an array of 9 days (0-8).
+    let mut fishes: Vec<u64> = vec![0; 9]; // counts of fishes N days old
+
+    // Add the existing fish to their respective "bin" in the array
+    for &age in ages {
+        fishes[age as usize] += 1;
+    }
+    recorder.frame(&|| school_picture(&fishes));
+
+    // For each day, the fish with 0 days until spawning reset their
+    // counter to 6 days until next spawn.
+    // New fish start with 8 days until next spawn.
+    for day in 1..(days + 1) {
+        let spawning = fishes.remove(0); // shift everything a day newer
+        fishes[6] += spawning; // restart counter
+        fishes.push(spawning); // Add new fish to day 8
+        trace!("day {day}: {} fish", fishes.iter().sum::<u64>());
+        recorder.frame(&|| school_picture(&fishes));
+    }
+    fishes.iter().sum()
+}
+
+/// How many fish have 0 to 8 days left, on a log scale. Fish about to spawn
+/// are yellow and newborns white.
+pub fn school_picture(fishes: &[u64]) -> Picture {
+    let bars: Vec<(u64, Color)> = fishes
+        .iter()
+        .enumerate()
+        .map(|(days, &count)| {
+            let color = match days {
+                0 => Color::YELLOW,
+                7 | 8 => Color::WHITE,
+                _ => Color::BLUE,
+            };
+            (count, color)
+        })
+        .collect();
+    log_bars(&bars, 48)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(day6::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(day6::part1(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(simulate(input, 256).into())
+    }
+
+    fn animate(input: &Self::Input, part: Part, recorder: &mut dyn Recorder) -> bool {
+        let days = match part {
+            Part::One => 80,
+            Part::Two => 256,
+        };
+        simulate_recorded(input, days, recorder);
+        true
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::check::differential;
+
+    #[test]
+    fn counts_match_simulating_each_fish() {
+        differential(
+            100,
+            6,
+            |rng| {
+                let ages = day6::parse(&generate::input(rng.range(0..=20), rng)).unwrap();
+                (ages, rng.range(0..=80))
+            },
+            |(ages, days)| day6::simulate(ages, *days) as u64,
+            |(ages, days)| simulate(ages, *days),
+        );
+    }
+}
@@ -0,0 +1,17 @@
+//! Random crab positions for `aoc gen`.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use common::random::Rng;
+
+/// Crabs in the real input
+pub const SIZE: usize = 1000;
+
+/// `size` positions below 2000 on one line. Like the real input most crabs
+/// are near the start, from taking the smaller of two random numbers.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let positions: Vec<String> = (0..size.max(1))
+        .map(|_| rng.range(0u32..=1999).min(rng.range(0..=1999)).to_string())
+        .collect();
+    positions.join(",") + "\n"
+}
@@ -0,0 +1,149 @@
+//! Day 7: The Treachery of Whales. Lining up crab submarines for the least
+//! fuel.
+
+#![cfg_attr(not(test), no_std)]
+
+extern crate alloc;
+
+pub mod generate;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use common::parse::{ParseError, Span};
+use common::random::Rng;
+use common::{Answer, AocError, Solution};
+
+/// Positions are below this, so no amount of crabs can need more fuel than
+/// fits in 64 bits
+pub const MAX_POSITION: u32 = 1 << 16;
+
+/// Crab positions on a single line, sorted
+pub fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+    let mut positions: Vec<u32> = Span::new(input)
+        .trim()
+        .split(",")
+        .map(|position| {
+            let position = position.trim();
+            match position.parse()? {
+                x if x < MAX_POSITION => Ok(x),
+                _ => Err(position.error(format!("expected a position below {MAX_POSITION}"))),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+    positions.sort();
+    Ok(positions)
+}
+
+/// Fuel for every crab to move to `target` when each step costs 1 more than
+/// the last
+pub fn cost_part2(target: u32, positions: &[u32]) -> u64 {
+    let mut fuel: u64 = 0;
+    for &value in positions {
+        let delta = value.abs_diff(target) as u64;
+        fuel += ((delta * delta) + delta) / 2;
+    }
+    fuel
+}
+
+/// Fuel for every crab to move to `target` at 1 per step
+pub fn cost_part1(target: u32, positions: &[u32]) -> u64 {
+    let mut fuel: u64 = 0;
+    for &value in positions {
+        fuel += value.abs_diff(target) as u64;
+    }
+    fuel
+}
+
+/// Fuel to align when each step costs 1, which is cheapest at the median.
+///
+/// `positions` must be sorted.
+pub fn cheapest_alignment(positions: &[u32]) -> u64 {
+    let median: u32 = positions[positions.len() / 2];
+    cost_part1(median, positions)
+}
+
+/// Fuel to align when each step costs 1 more than the last. Searches outwards
+/// from the mean while the cost keeps falling, the cheapest target is within
+/// a step of it.
+///
+/// `positions` must be sorted.
+pub fn cheapest_increasing_alignment(positions: &[u32]) -> u64 {
+    // start at the mean, rounded down
+    let max_pos: u32 = positions[positions.len() - 1];
+    let total: u64 = positions.iter().map(|&x| x as u64).sum();
+    let mean = (total / positions.len() as u64) as u32;
+    let min_pos: u32 = positions[0];
+
+    let mut min_cost = cost_part2(mean, positions);
+    // Nothing to search past the ends of the number range
+    if let Some(mut target) = mean.checked_add(1) {
+        let mut target_cost = cost_part2(target, positions);
+        while (target_cost <= min_cost) && (target < max_pos) {
+            min_cost = target_cost;
+            target += 1;
+            target_cost = cost_part2(target, positions);
+        }
+    }
+
+    if let Some(mut target) = mean.checked_sub(1) {
+        let mut target_cost = cost_part2(target, positions);
+        while (target_cost <= min_cost) && (target > min_pos) {
+            min_cost = target_cost;
+            target -= 1;
+            target_cost = cost_part2(target, positions);
+        }
+    }
+
+    min_cost
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    /// Crab positions, sorted
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(cheapest_alignment(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(cheapest_increasing_alignment(input).into())
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn far_apart() {
+        let positions = parse("0,0,0,65535").unwrap();
+        assert_eq!(cheapest_alignment(&positions), 65535);
+        assert_eq!(
+            cheapest_increasing_alignment(&positions),
+            (0..=65535)
+                .map(|target| cost_part2(target, &positions))
+                .min()
+                .unwrap()
+        );
+        assert!(parse("1,65536").is_err());
+    }
+
+    #[test]
+    fn means_at_the_ends() {
+        assert_eq!(cheapest_increasing_alignment(&parse("0,0,1").unwrap()), 1);
+        assert_eq!(cheapest_increasing_alignment(&[u32::MAX; 3]), 0);
+    }
+}
@@ -0,0 +1,44 @@
+//! Random seven-segment notes for `aoc gen`.
+
+use common::random::Rng;
+
+/// Entries in the real input
+pub const SIZE: usize = 200;
+
+/// The segments each digit lights up on a display that's wired correctly
+const DIGITS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// `digit` on a display wired up by `wiring`, with its segments in a random
+/// order
+fn scramble(digit: usize, wiring: &[char], rng: &mut Rng) -> String {
+    let mut segments: Vec<char> = DIGITS[digit]
+        .bytes()
+        .map(|x| wiring[(x - b'a') as usize])
+        .collect();
+    rng.shuffle(&mut segments);
+    segments.into_iter().collect()
+}
+
+/// `size` entries, each the ten digits and then four displayed ones on a
+/// display with its own random wiring
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let mut text = String::new();
+    for _ in 0..size {
+        let mut wiring: Vec<char> = ('a'..='g').collect();
+        rng.shuffle(&mut wiring);
+
+        let mut digits: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut digits);
+        let patterns: Vec<String> = digits
+            .iter()
+            .map(|&digit| scramble(digit, &wiring, rng))
+            .collect();
+        let display: Vec<String> = (0..4)
+            .map(|_| scramble(rng.below(10) as usize, &wiring, rng))
+            .collect();
+        text.push_str(&format!("{} | {}\n", patterns.join(" "), display.join(" ")));
+    }
+    text
+}
@@ -0,0 +1,326 @@
+//! Day 8: Seven Segment Search. Unscrambling the wiring of four digit
+//! displays.
+
+pub mod generate;
+
+use common::parse::{separated_pair, ParseError, Span};
+use common::random::Rng;
+use common::{trace, Answer, AocError, Solution};
+
+/// One entry of the notes, the ten scrambled digit patterns and the four
+/// digits shown on the display
+pub struct DigitSet {
+    /// Sorted segments of each digit, indexed by the digit
+    patterns: [Vec<char>; 10],
+    display: Vec<String>,
+}
+
+impl DigitSet {
+    /// Work out which pattern is which digit from a `patterns | display` line
+    pub fn from_pattern(line: Span) -> Result<Self, ParseError> {
+        let (patterns, display) = separated_pair(line, "|")?;
+        if patterns.split_whitespace().count() != 10 {
+            return Err(patterns.error("expected 10 patterns"));
+        }
+        let set = DigitSet {
+            patterns: PatternPossibility::from_patterns(patterns.as_str())
+                .into_resolved_array()
+                .ok_or_else(|| patterns.error("can't work out which pattern is which digit"))?,
+            display: display.split_whitespace().map(|x| x.to_string()).collect(),
+        };
+
+        // every displayed digit has to be one of the patterns for to_number
+        for digit in display.split_whitespace() {
+            if set.digit_number(digit.as_str()).is_none() {
+                return Err(digit.error("expected one of the patterns"));
+            }
+        }
+        Ok(set)
+    }
+
+    /// Segments of each digit, `patterns()[7]` lights up a 7
+    pub fn patterns(&self) -> &[Vec<char>; 10] {
+        &self.patterns
+    }
+
+    /// Scrambled segments of each digit on the display
+    pub fn display(&self) -> &[String] {
+        &self.display
+    }
+
+    /// The number shown on the display
+    pub fn to_number(&self) -> u64 {
+        let mut accumulator: u64 = 0;
+        for (index, number) in self.display.iter().enumerate() {
+            // number * 10 ^ place
+            accumulator += (self.digit_number(number).unwrap() as u64)
+                * u64::pow(10, (self.display.len() - 1 - index) as u32);
+        }
+        accumulator
+    }
+
+    /// Helper function for to_number. Converts each digit
+    fn digit_number(&self, number: &str) -> Option<u8> {
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            if pattern.len() != number.len() {
+                continue;
+            }
+            trace!("looking for {number:?} in {pattern:?}");
+            let mut accumulator = 0;
+            for num_seg in number.chars() {
+                for &pat_seg in pattern {
+                    if num_seg == pat_seg {
+                        accumulator += 1;
+                    }
+                }
+            }
+            if accumulator == number.len() {
+                return Some(index as u8);
+            }
+            // This would work if I bothered to sort the segments
+            // if number.chars().zip(pattern).filter(|(x, y)| x == *y).count() == pattern.len() {
+            //     return Some(index as u8);
+            // }
+        }
+        None
+    }
+}
+
+/// Digit patterns while working out which is which. The digits with a unique
+/// number of segments are known straight away, the rest are resolved by which
+/// known digits they contain.
+pub struct PatternPossibility {
+    one: Vec<char>,   // Known: only with 2 segments
+    four: Vec<char>,  // Known: only with 4 segments
+    seven: Vec<char>, // Known: only with 3 segments
+    eight: Vec<char>, // Known: only with 7 segments
+    others: Vec<Vec<char>>,
+}
+
+impl PatternPossibility {
+    fn new() -> Self {
+        PatternPossibility {
+            one: Vec::new(),
+            four: Vec::new(),
+            seven: Vec::new(),
+            eight: Vec::new(),
+            others: Vec::new(),
+        }
+    }
+
+    /// Sort the whitespace separated patterns by what's known about them
+    pub fn from_patterns(input: &str) -> Self {
+        let patterns: Vec<String> = input.split_whitespace().map(|x| x.to_owned()).collect();
+        let mut wip = PatternPossibility::new();
+        for pattern in patterns {
+            let mut char_pattern: Vec<char> = pattern.chars().collect();
+            char_pattern.sort();
+            match pattern.len() {
+                2 => wip.one = char_pattern,
+                3 => wip.seven = char_pattern,
+                4 => wip.four = char_pattern,
+                7 => wip.eight = char_pattern,
+                _ => wip.others.push(char_pattern),
+            }
+        }
+        wip
+    }
+
+    /// Each digit's segments indexed by the digit, or `None` if the patterns
+    /// don't make up the ten digits
+    pub fn into_resolved_array(mut self) -> Option<[Vec<char>; 10]> {
+        let nine = self.resolve_nine()?;
+        let three = self.resolve_three()?;
+
+        // Order matters for the following
+        let zero = self.resolve_zero()?;
+        let six = self.resolve_six()?;
+        let five = self.resolve_five(&six)?;
+        let two = self.others.pop()?;
+        Some([
+            zero, self.one, two, three, self.four, five, six, self.seven, self.eight, nine,
+        ])
+    }
+
+    /// Nine is the only unresolved segment that includes 4
+    fn resolve_nine(&mut self) -> Option<Vec<char>> {
+        for (index, pattern) in self.others.iter().enumerate() {
+            let mut match_counter = 0;
+            for segment in pattern {
+                for four_seg in &self.four {
+                    if segment == four_seg {
+                        match_counter += 1;
+                    }
+                }
+            }
+            if match_counter == self.four.len() {
+                return Some(self.others.remove(index));
+            }
+
+            // This stops after the number of digits in four, so can't make a
+            // complete match unless we're lucky enough to match the first 4
+            // if pattern
+            //     .iter()
+            //     .zip(&self.four)
+            //     .filter(|(x, y)| x == y)
+            //     .count()
+            //     == self.four.len()
+            // {
+            //     return Some(self.others.remove(index));
+            // }
+        }
+        None
+    }
+
+    /// Three is the only unresolved segment that includes 7 and has len of 5
+    fn resolve_three(&mut self) -> Option<Vec<char>> {
+        for (index, pattern) in self.others.iter().enumerate() {
+            if pattern.len() != 5 {
+                continue;
+            }
+            let mut match_counter = 0;
+            for segment in pattern {
+                for seven_seg in &self.seven {
+                    if segment == seven_seg {
+                        match_counter += 1;
+                    }
+                }
+            }
+            if match_counter == self.seven.len() {
+                return Some(self.others.remove(index));
+            }
+            // if pattern
+            //     .iter()
+            //     .zip(&self.seven)
+            //     .filter(|(x, y)| x == y)
+            //     .count()
+            //     == self.seven.len()
+            // {
+            //     return Some(self.others.remove(index));
+            // }
+        }
+        None
+    }
+
+    /// Zero is the only unresolved segment that includes 7 and has a length of
+    /// 6, other than 9
+    fn resolve_zero(&mut self) -> Option<Vec<char>> {
+        for (index, pattern) in self.others.iter().enumerate() {
+            if pattern.len() != 6 {
+                continue;
+            }
+
+            let mut match_counter = 0;
+            for segment in pattern {
+                for seven_seg in &self.seven {
+                    if segment == seven_seg {
+                        match_counter += 1;
+                    }
+                }
+            }
+            if match_counter == self.seven.len() {
+                return Some(self.others.remove(index));
+            }
+            // if pattern
+            //     .iter()
+            //     .zip(&self.seven)
+            //     .filter(|(x, y)| x == y)
+            //     .count()
+            //     == self.seven.len()
+            // {
+            //     return Some(self.others.remove(index));
+            // }
+        }
+        None
+    }
+
+    /// after resolving 0 and 9, 6 is the only remaining 6-lit segment.
+    fn resolve_six(&mut self) -> Option<Vec<char>> {
+        for (index, pattern) in self.others.iter().enumerate() {
+            if pattern.len() == 6 {
+                return Some(self.others.remove(index));
+            }
+        }
+        None
+    }
+
+    /// five is only unresolved segment wholly contained within 6
+    fn resolve_five(&mut self, six: &[char]) -> Option<Vec<char>> {
+        for (index, pattern) in self.others.iter().enumerate() {
+            trace!("looking for all but 1 segment of {six:?} in {pattern:?}");
+            let mut match_counter = 0;
+            for segment in pattern {
+                for six_seg in six {
+                    if segment == six_seg {
+                        match_counter += 1;
+                    }
+                }
+            }
+            // "5" should have 5 segments match within "6"
+            if match_counter == 5 {
+                return Some(self.others.remove(index));
+            }
+            // if pattern.iter().zip(six).filter(|(x, y)| x == y).count() == pattern.len() {
+            //     return Some(self.others.remove(index));
+            // }
+        }
+        None
+    }
+}
+
+/// One [DigitSet] per line
+pub fn parse(input: &str) -> Result<Vec<DigitSet>, ParseError> {
+    Span::new(input)
+        .lines()
+        .map(DigitSet::from_pattern)
+        .collect()
+}
+
+/// count 1,4,7,8 in outputs, the digits with a unique number of segments
+pub fn count_unique_digits(sets: &[DigitSet]) -> u64 {
+    let mut p1_count: u64 = 0;
+    for set in sets.iter() {
+        for value in &set.display {
+            for pattern in [
+                &set.patterns[1],
+                &set.patterns[4],
+                &set.patterns[7],
+                &set.patterns[8],
+            ] {
+                // take advantage that these matches are based solely on length
+                if value.len() == pattern.len() {
+                    p1_count += 1;
+                }
+            }
+        }
+    }
+    p1_count
+}
+
+/// Sum of all sets' displays
+pub fn display_sum(sets: &[DigitSet]) -> u64 {
+    sets.iter().fold(0, |acc, set| acc + set.to_number())
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Vec<DigitSet>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(count_unique_digits(input).into())
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(display_sum(input).into())
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
@@ -0,0 +1,45 @@
+//! Random height maps for `aoc gen`.
+
+use common::random::Rng;
+
+/// Width and height of the real input
+pub const SIZE: usize = 100;
+
+/// A `size` by `size` map of at least 3 basins. Each basin slopes up from a
+/// random low point, with ridges of 9 where basins meet, the way the real
+/// input looks.
+pub fn input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(5);
+    // Low points are kept apart so that each one is lower than its neighbors
+    let mut low_points: Vec<(usize, usize)> = Vec::new();
+    while low_points.len() < (size * size / 60).max(3) {
+        let point = (
+            rng.below(size as u64) as usize,
+            rng.below(size as u64) as usize,
+        );
+        let apart = |&(y, x): &(usize, usize)| y.abs_diff(point.0) + x.abs_diff(point.1) > 2;
+        if low_points.iter().all(apart) {
+            low_points.push(point);
+        }
+    }
+
+    let mut text = String::new();
+    for row in 0..size {
+        for column in 0..size {
+            let mut distances: Vec<f64> = low_points
+                .iter()
+                .map(|&(y, x)| f64::hypot(y as f64 - row as f64, x as f64 - column as f64))
+                .collect();
+            distances.sort_by(f64::total_cmp);
+            let height = match distances[..] {
+                [nearest, next, ..] if next - nearest < 0.6 => 9,
+                [0.0, ..] => 0,
+                [nearest, ..] => (nearest as u8 + rng.below(2) as u8).min(9),
+                [] => unreachable!("there's always a low point"),
+            };
+            text.push(char::from(b'0' + height));
+        }
+        text.push('\n');
+    }
+    text
+}
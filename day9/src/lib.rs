@@ -0,0 +1,127 @@
+//! Day 9: Smoke Basin. Finding the low points and basins of a height map.
+
+pub mod generate;
+
+use common::grid::Position;
+use common::parse::{digit_grid, ParseError};
+use common::random::Rng;
+use common::render::{Cell, Color, Picture};
+use common::search::{dfs, Control, Graph};
+use common::{debug, Answer, AocError, Grid, Part, Solution};
+
+/// Points lower than all of their neighbors
+pub fn low_points(map: &Grid<u32>) -> Vec<Position> {
+    let low_points: Vec<Position> = map
+        .positions()
+        .filter(|&point| {
+            map.neighbors4(point)
+                .all(|neighbor| map[point] < map[neighbor])
+        })
+        .collect();
+    debug!("low points {low_points:?}");
+    low_points
+}
+
+/// The height map, one digit per point
+pub fn parse(input: &str) -> Result<Grid<u32>, ParseError> {
+    digit_grid(input)
+}
+
+/// Total risk of the low points, each being its height plus 1
+pub fn risk_level_sum(map: &Grid<u32>) -> u32 {
+    low_points(map).iter().fold(0, |acc, &x| acc + map[x] + 1)
+}
+
+/// Flowing uphill from a point to any neighbor that's higher but not 9
+struct Uphill<'a>(&'a Grid<u32>);
+
+impl Graph for Uphill<'_> {
+    type Node = Position;
+    type Cost = u32;
+
+    fn successors(&self, &point: &Position) -> impl Iterator<Item = (Position, u32)> {
+        let map = self.0;
+        map.neighbors4(point)
+            .filter(move |&neighbor| map[neighbor] > map[point] && map[neighbor] < 9)
+            .map(|neighbor| (neighbor, 1))
+    }
+}
+
+/// The points of the basin around each low point, in the order of
+/// [low_points] and each starting with its low point. A basin is everything
+/// that flows down to its low point, bounded by height 9.
+pub fn basins(map: &Grid<u32>) -> Vec<Vec<Position>> {
+    // Take each low point as an origin point and flood fill everything
+    // reachable going uphill. A point can be reached from more than one of its
+    // neighbors, so each basin keeps its own visited set.
+    low_points(map)
+        .into_iter()
+        .map(|low_point| {
+            let mut basin: Vec<Position> = Vec::new();
+            let mut visited = Grid::new(map.width(), map.height(), false);
+            dfs(&Uphill(map), low_point, &mut visited, |step| {
+                basin.push(*step.node());
+                Control::Continue
+            });
+            basin
+        })
+        .collect()
+}
+
+/// Each basin in its own color with the low points picked out in white, and
+/// the 9s that wall them off in gray
+pub fn basin_picture(map: &Grid<u32>) -> Picture {
+    let mut picture = Picture::from_grid(map, |&height| {
+        Cell::new(
+            char::from_digit(height, 10).unwrap_or('?'),
+            Color::BACKGROUND,
+        )
+    });
+    for (index, basin) in basins(map).iter().enumerate() {
+        for &position in basin {
+            picture.cells[position].color = Color::palette(index);
+        }
+        picture.cells[basin[0]].color = Color::WHITE;
+    }
+    for position in map.positions().filter(|&x| map[x] == 9) {
+        picture.cells[position].color = Color::GRAY;
+    }
+    picture
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Grid<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer, AocError> {
+        Ok(risk_level_sum(input).into())
+    }
+
+    /// Product of the three largest basin sizes
+    fn part2(input: &Self::Input) -> Result<Answer, AocError> {
+        let mut sizes: Vec<usize> = basins(input).iter().map(|x| x.len()).collect();
+        if sizes.len() < 3 {
+            return Err(AocError::Semantic(format!(
+                "need at least 3 basins, found {}",
+                sizes.len()
+            )));
+        }
+        let slen = sizes.len() - 1;
+        sizes.sort();
+        Ok((sizes[slen] * sizes[slen - 1] * sizes[slen - 2]).into())
+    }
+
+    fn picture(input: &Self::Input, _part: Part) -> Option<Picture> {
+        Some(basin_picture(input))
+    }
+
+    fn generate(size: Option<usize>, rng: &mut Rng) -> Option<String> {
+        Some(generate::input(size.unwrap_or(generate::SIZE), rng))
+    }
+}
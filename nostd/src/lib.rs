@@ -0,0 +1,71 @@
+//! The days that only need `alloc`, built without `std`.
+//!
+//! This crate is `no_std` and uses `common` and the days below without their
+//! default `std` feature, so anything in them that needs an operating system
+//! fails to build. Test it on its own, otherwise the other workspace members
+//! turn `std` back on through feature unification:
+//!
+//! ```text
+//! cargo test -p nostd
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use common::{register, Answer, AocError, DynSolution, Part};
+
+/// Every solver that works without `std`
+pub fn solutions() -> Vec<Box<dyn DynSolution>> {
+    vec![
+        register::<day6_2::Day6>(),
+        register::<day7::Day7>(),
+        register::<day16::Day16>(),
+    ]
+}
+
+/// Answers to both parts of `day`, or `None` if it needs `std`
+pub fn solve(day: u8, input: &str) -> Option<Result<[Answer; 2], AocError>> {
+    let solution = solutions().into_iter().find(|x| x.day() == day)?;
+    Some(solution.parse(input).and_then(|parsed| {
+        let [one, two] = Part::BOTH;
+        Ok([
+            solution.solve(parsed.as_ref(), one)?,
+            solution.solve(parsed.as_ref(), two)?,
+        ])
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    fn check(day: u8, input: &str, answers: &str) {
+        let [one, two] = solve(day, input).unwrap().unwrap();
+        assert_eq!(format!("1: {one}\n2: {two}\n"), answers, "day {day}");
+    }
+
+    #[test]
+    fn recorded_answers() {
+        check(
+            6,
+            include_str!("../../day6/input.txt"),
+            include_str!("../../day6/answers.txt"),
+        );
+        check(
+            7,
+            include_str!("../../day7/input.txt"),
+            include_str!("../../day7/answers.txt"),
+        );
+        check(
+            16,
+            include_str!("../../day16/input.txt"),
+            include_str!("../../day16/answers.txt"),
+        );
+        assert!(solve(15, "").is_none());
+    }
+}